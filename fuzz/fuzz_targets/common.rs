@@ -0,0 +1,215 @@
+// Not every fuzz target uses every helper
+#![allow(dead_code)]
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    pixelcolor::BinaryColor,
+    prelude::{DrawTarget, OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use u8g2_fonts::{
+    types::{
+        CellWidth, FixedWidth, FontColor, FontRotation, HorizontalAlignment, KerningPair,
+        TextDecorations, VerticalPosition, WrappedAlignment,
+    },
+    FontRenderer,
+};
+
+/// A display that accepts and discards everything.
+pub struct NullDisplay;
+
+impl DrawTarget for NullDisplay {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        pixels.into_iter().for_each(drop);
+        Ok(())
+    }
+}
+
+impl OriginDimensions for NullDisplay {
+    fn size(&self) -> Size {
+        Size::new(128, 64)
+    }
+}
+
+const KERNING: &[KerningPair] = &[
+    KerningPair::new('A', 'V', -2),
+    KerningPair::new('T', 'o', -3),
+    KerningPair::new('f', 'i', 1),
+];
+
+const COLORS: [FontColor<BinaryColor>; 4] = [
+    FontColor::Transparent(BinaryColor::On),
+    FontColor::WithBackground {
+        fg: BinaryColor::On,
+        bg: BinaryColor::Off,
+    },
+    FontColor::WithOutline {
+        fg: BinaryColor::On,
+        outline: BinaryColor::Off,
+    },
+    FontColor::WithShadow {
+        fg: BinaryColor::On,
+        shadow: BinaryColor::Off,
+    },
+];
+
+/// Applies a combination of all rendering options, selected by the bits of `config`.
+pub fn configure(font: FontRenderer<'_>, config: u16) -> FontRenderer<'_> {
+    let bit = |n: u32| config & (1 << n) != 0;
+
+    let rotation = match config & 0b11 {
+        0 => FontRotation::Rotate0,
+        1 => FontRotation::Rotate90,
+        2 => FontRotation::Rotate180,
+        _ => FontRotation::Rotate270,
+    };
+    let fixed_width = match (config >> 8) & 0b11 {
+        0 => FixedWidth::Proportional,
+        1 => FixedWidth::Digits(CellWidth::WidestDigit),
+        2 => FixedWidth::AllGlyphs(CellWidth::Pixels(7)),
+        _ => FixedWidth::AllGlyphs(CellWidth::WidestDigit),
+    };
+
+    font.with_rotation(rotation)
+        .with_scale(u32::from((config >> 2) & 0b11) + 1)
+        .with_synthetic_bold(bit(4))
+        .with_synthetic_italic(bit(5))
+        .with_kerning(if bit(6) { KERNING } else { &[] })
+        .with_letter_spacing(if bit(7) { -1 } else { 0 })
+        .with_fixed_width(fixed_width)
+        .with_decorations(
+            TextDecorations::NONE
+                .with_underline(bit(10))
+                .with_strikethrough(bit(11)),
+        )
+        .with_emulated_background(bit(12))
+        .with_replacement_char(if bit(13) { Some('\u{fffd}') } else { None })
+        .with_ignore_unknown_chars(true)
+}
+
+/// Renders and measures text through every text rendering function, in all colors.
+pub fn render_text(font: &FontRenderer, text: &str) {
+    let position = Point::new(64, 32);
+    let area = Rectangle::new(Point::new(4, 4), Size::new(120, 56));
+
+    for color in COLORS {
+        let _ = font.render(
+            text,
+            position,
+            VerticalPosition::Baseline,
+            color,
+            &mut NullDisplay,
+        );
+        let _ = font.render_aligned(
+            text,
+            position,
+            VerticalPosition::Center,
+            HorizontalAlignment::Right,
+            color,
+            &mut NullDisplay,
+        );
+        let _ = font.render_wrapped(
+            text,
+            area,
+            VerticalPosition::Top,
+            WrappedAlignment::Justify,
+            color,
+            &mut NullDisplay,
+        );
+        let _ = font.render_truncated(
+            text,
+            position,
+            40,
+            VerticalPosition::Bottom,
+            color,
+            &mut NullDisplay,
+        );
+    }
+
+    let _ = font.get_rendered_dimensions(text, position, VerticalPosition::Baseline);
+    let _ = font.get_rendered_dimensions_aligned(
+        text,
+        position,
+        VerticalPosition::Center,
+        HorizontalAlignment::Center,
+    );
+    let _ = font.get_rendered_dimensions_wrapped(
+        text,
+        area,
+        VerticalPosition::Top,
+        WrappedAlignment::Justify,
+    );
+    let _ = font.get_rendered_dimensions_truncated(text, position, 40, VerticalPosition::Top);
+
+    for glyph in font.layout(
+        text,
+        position,
+        VerticalPosition::Baseline,
+        HorizontalAlignment::Center,
+    ) {
+        if glyph.is_err() {
+            break;
+        }
+    }
+
+    if let Ok(prepared) = font.prepare::<32>(text, VerticalPosition::Top, HorizontalAlignment::Left)
+    {
+        for color in COLORS {
+            let _ = prepared.draw(position, color, &mut NullDisplay);
+        }
+    }
+
+    let mut buffer = [0; 1024];
+    let _ = font.render_to_bitmap(text, VerticalPosition::Top, &mut buffer, 32);
+}
+
+/// Renders every character the font could possibly contain, with all render modes.
+pub fn render_everything(font: FontRenderer) {
+    let chars = (0u32..=0xff)
+        .chain((0x100..=0xffff).step_by(61))
+        .filter_map(char::from_u32);
+
+    // Strings of neighbouring characters, with line breaks and spaces to wrap at
+    let text: String = chars
+        .clone()
+        .enumerate()
+        .map(|(i, ch)| match i % 17 {
+            7 => ' ',
+            16 => '\n',
+            _ => ch,
+        })
+        .collect();
+    for config in [0, 0x3ff4, 0x157b, 0x2aa6] {
+        let font = configure(font.clone(), config);
+        for line in text.split_inclusive('\n').collect::<Vec<_>>().chunks(4) {
+            render_text(&font, &line.concat());
+        }
+    }
+
+    for rotation in [FontRotation::Rotate0, FontRotation::Rotate90] {
+        let font = font
+            .clone()
+            .with_rotation(rotation)
+            .with_ignore_unknown_chars(true);
+        for ch in chars.clone() {
+            for color in COLORS {
+                let _ = font.render(
+                    ch,
+                    Point::new(64, 32),
+                    VerticalPosition::Baseline,
+                    color,
+                    &mut NullDisplay,
+                );
+            }
+            let _ = font.get_rendered_dimensions(ch, Point::new(64, 32), VerticalPosition::Top);
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Feeds arbitrary data into the validated font constructor.
+//!
+//! Whatever passes validation has to render without panicking.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use u8g2_fonts::FontRenderer;
+
+mod common;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(font) = FontRenderer::try_from_data(data) {
+        common::render_everything(font);
+    }
+});
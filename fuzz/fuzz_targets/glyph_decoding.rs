@@ -0,0 +1,29 @@
+//! Corrupts the glyph data of real fonts while keeping the tables intact,
+//! to stress the glyph decoder with arbitrary bitmaps and glyph headers.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use u8g2_fonts::{fonts, Font, FontRenderer};
+
+mod common;
+
+const FONTS: &[&[u8]] = &[
+    fonts::u8g2_font_ncenB14_tr::DATA,
+    fonts::u8g2_font_10x20_mf::DATA,
+    fonts::u8g2_font_10x20_t_greek::DATA,
+];
+
+fuzz_target!(|input: (u8, Vec<(u16, u8)>)| {
+    let (font_index, corruptions) = input;
+    let mut data = FONTS[usize::from(font_index) % FONTS.len()].to_vec();
+
+    for (pos, mask) in corruptions {
+        let pos = usize::from(pos) % data.len();
+        data[pos] ^= mask;
+    }
+
+    if let Ok(font) = FontRenderer::try_from_data(&data) {
+        common::render_everything(font);
+    }
+});
@@ -0,0 +1,27 @@
+//! Renders arbitrary text with arbitrary combinations of rendering options.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use u8g2_fonts::{fonts, FontRenderer};
+
+mod common;
+
+static FONTS: [FontRenderer; 4] = [
+    FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>(),
+    FontRenderer::new::<fonts::u8g2_font_10x20_mf>(),
+    FontRenderer::new::<fonts::u8g2_font_helvR08_tr>(),
+    FontRenderer::new::<fonts::u8g2_font_10x20_t_greek>(),
+];
+
+static FALLBACK: FontRenderer = FontRenderer::new::<fonts::u8g2_font_unifont_t_symbols>();
+
+fuzz_target!(|input: (u8, u16, bool, &str)| {
+    let (font_index, config, with_fallback, text) = input;
+    let mut font = FONTS[usize::from(font_index) % FONTS.len()].clone();
+    if with_fallback {
+        font = font.with_fallback(&FALLBACK);
+    }
+
+    common::render_text(&common::configure(font, config), text);
+});
@@ -0,0 +1,229 @@
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    image::ImageDrawable,
+    pixelcolor::{BinaryColor, PixelColor},
+    prelude::{Dimensions, DrawTarget, OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Text that got rendered into a packed 1 bit per pixel buffer.
+///
+/// Created by [`FontRenderer::render_to_bitmap()`](crate::FontRenderer::render_to_bitmap).
+///
+/// Every row of the bitmap starts at a multiple of the [stride](Bitmap::stride) in the buffer.
+/// Inside of a row, every byte holds eight pixels, the leftmost pixel in the most significant bit.
+///
+/// Drawing the bitmap only draws the pixels of the text, in the [color](Bitmap::with_color)
+/// of the bitmap; the remaining pixels are left untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitmap<'b, C = BinaryColor> {
+    data: &'b [u8],
+    size: Size,
+    stride: usize,
+    offset: Point,
+    color: C,
+}
+
+impl<'b> Bitmap<'b, BinaryColor> {
+    pub(crate) const fn new(data: &'b [u8], size: Size, stride: usize, offset: Point) -> Self {
+        Self {
+            data,
+            size,
+            stride,
+            offset,
+            color: BinaryColor::On,
+        }
+    }
+
+    /// The minimum stride of a bitmap with the given width, in bytes.
+    pub const fn min_stride(width: u32) -> usize {
+        width.div_ceil(8) as usize
+    }
+
+    /// The minimum length of the buffer for a bitmap of the given size and stride, in bytes.
+    pub const fn required_buffer_len(size: Size, stride: usize) -> usize {
+        if size.height == 0 {
+            0
+        } else {
+            (size.height as usize - 1) * stride + Self::min_stride(size.width)
+        }
+    }
+}
+
+impl<'b, C> Bitmap<'b, C> {
+    /// Changes the color the bitmap gets drawn in.
+    pub fn with_color<T: PixelColor>(self, color: T) -> Bitmap<'b, T> {
+        Bitmap {
+            data: self.data,
+            size: self.size,
+            stride: self.stride,
+            offset: self.offset,
+            color,
+        }
+    }
+
+    /// The packed pixel data of the bitmap.
+    pub const fn data(&self) -> &'b [u8] {
+        self.data
+    }
+
+    /// The distance between the starts of two rows in [`data()`](Bitmap::data), in bytes.
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The position of the top left corner of the bitmap, relative to the position
+    /// the text was rendered to.
+    ///
+    /// Drawing the bitmap at `position + offset` produces the same pixels as rendering
+    /// the text to `position`.
+    pub const fn offset(&self) -> Point {
+        self.offset
+    }
+
+    /// Whether the pixel at the given position of the bitmap belongs to the text.
+    pub fn is_set(&self, point: Point) -> bool {
+        if point.x < 0
+            || point.y < 0
+            || point.x >= self.size.width as i32
+            || point.y >= self.size.height as i32
+        {
+            return false;
+        }
+
+        let (x, y) = (point.x as usize, point.y as usize);
+        self.data[y * self.stride + x / 8] & (0x80 >> (x % 8)) != 0
+    }
+}
+
+impl<C> OriginDimensions for Bitmap<'_, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C: PixelColor> ImageDrawable for Bitmap<'_, C> {
+    type Color = C;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.draw_sub_image(target, &self.bounding_box())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        let pixels = (0..area.size.height as i32).flat_map(|y| {
+            (0..area.size.width as i32).filter_map(move |x| {
+                let p = Point::new(x, y);
+                self.is_set(area.top_left + p)
+                    .then_some(Pixel(p, self.color))
+            })
+        });
+        target.draw_iter(pixels)
+    }
+}
+
+/// Renders into the buffer of a [`Bitmap`].
+pub(crate) struct BitmapTarget<'b> {
+    data: &'b mut [u8],
+    size: Size,
+    stride: usize,
+}
+
+impl<'b> BitmapTarget<'b> {
+    /// Clears the part of the buffer that belongs to the bitmap.
+    pub fn new(data: &'b mut [u8], size: Size, stride: usize) -> Self {
+        let row_len = Bitmap::min_stride(size.width);
+        for y in 0..size.height as usize {
+            data[y * stride..][..row_len].fill(0);
+        }
+        Self { data, size, stride }
+    }
+
+    pub fn into_data(self) -> &'b [u8] {
+        self.data
+    }
+}
+
+impl OriginDimensions for BitmapTarget<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for BitmapTarget<'_> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+        for Pixel(p, color) in pixels {
+            if !bounding_box.contains(p) {
+                continue;
+            }
+            let (x, y) = (p.x as usize, p.y as usize);
+            let byte = &mut self.data[y * self.stride + x / 8];
+            match color {
+                BinaryColor::On => *byte |= 0x80 >> (x % 8),
+                BinaryColor::Off => *byte &= !(0x80 >> (x % 8)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::vec;
+
+    use super::*;
+
+    #[test]
+    fn packs_pixels_msb_first() {
+        let mut data = [0xff; 6];
+        let mut target = BitmapTarget::new(&mut data, Size::new(10, 2), 3);
+        target
+            .draw_iter([
+                Pixel(Point::new(0, 0), BinaryColor::On),
+                Pixel(Point::new(9, 0), BinaryColor::On),
+                Pixel(Point::new(3, 1), BinaryColor::On),
+                Pixel(Point::new(10, 1), BinaryColor::On),
+                Pixel(Point::new(-1, 1), BinaryColor::On),
+            ])
+            .unwrap();
+
+        // Bytes outside of the rows stay untouched
+        assert_eq!(target.into_data(), [0x80, 0x40, 0xff, 0x10, 0x00, 0xff]);
+    }
+
+    #[test]
+    fn computes_buffer_sizes() {
+        assert_eq!(Bitmap::min_stride(0), 0);
+        assert_eq!(Bitmap::min_stride(8), 1);
+        assert_eq!(Bitmap::min_stride(9), 2);
+        assert_eq!(Bitmap::required_buffer_len(Size::new(9, 3), 4), 10);
+        assert_eq!(Bitmap::required_buffer_len(Size::new(9, 0), 4), 0);
+    }
+
+    #[test]
+    fn reads_pixels() {
+        let data = vec![0x81, 0x00, 0x00, 0x40];
+        let bitmap = Bitmap::new(&data, Size::new(8, 2), 2, Point::zero());
+        assert!(bitmap.is_set(Point::new(0, 0)));
+        assert!(bitmap.is_set(Point::new(7, 0)));
+        assert!(!bitmap.is_set(Point::new(1, 0)));
+        assert!(!bitmap.is_set(Point::new(9, 1)));
+        assert!(!bitmap.is_set(Point::new(0, 2)));
+    }
+}
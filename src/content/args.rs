@@ -1,4 +1,4 @@
-use core::{mem, ops::Range};
+use core::{convert::Infallible, mem, ops::Range};
 
 use crate::{
     font_reader::FontReader,
@@ -29,6 +29,81 @@ impl<'a> Content for core::fmt::Arguments<'a> {
     fn line_dimensions_iterator(&self) -> ArgsLineDimensionsIterator<'a> {
         ArgsLineDimensionsIterator::new(*self)
     }
+
+    type CharIter = ArgsCharIterator<'a>;
+
+    fn char_iterator(&self) -> ArgsCharIterator<'a> {
+        ArgsCharIterator::new(*self)
+    }
+}
+
+// Same reasoning as for the line dimensions: buffer a few characters,
+// and format the arguments again once they are used up.
+const NUM_BUFFERED_CHARS: usize = 16;
+
+pub struct ArgsCharIterator<'a> {
+    args: core::fmt::Arguments<'a>,
+    buffer: [char; NUM_BUFFERED_CHARS],
+    buffer_range: Range<usize>,
+    next_char: usize,
+    finished: bool,
+}
+
+impl<'a> ArgsCharIterator<'a> {
+    pub fn new(args: core::fmt::Arguments<'a>) -> Self {
+        Self {
+            args,
+            buffer: ['\0'; NUM_BUFFERED_CHARS],
+            buffer_range: 0..0,
+            next_char: 0,
+            finished: false,
+        }
+    }
+
+    fn regenerate_buffer(&mut self, range_start: usize) {
+        let range_end = range_start + NUM_BUFFERED_CHARS;
+        let mut char_num: usize = 0;
+        let mut has_more = false;
+
+        FormatArgsReader::new(|ch| -> Result<bool, Infallible> {
+            if char_num >= range_end {
+                // break if we are past the desired range
+                has_more = true;
+                return Ok(false);
+            }
+            if let Some(array_pos) = char_num.checked_sub(range_start) {
+                self.buffer[array_pos] = ch;
+            }
+            char_num += 1;
+            Ok(true)
+        })
+        .process_args(self.args)
+        .ok();
+
+        self.finished = !has_more;
+        self.buffer_range = range_start..char_num.max(range_start);
+    }
+}
+
+impl Iterator for ArgsCharIterator<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if !self.buffer_range.contains(&self.next_char) {
+            if self.finished {
+                return None;
+            }
+
+            self.regenerate_buffer(self.next_char);
+            if !self.buffer_range.contains(&self.next_char) {
+                return None;
+            }
+        }
+
+        let ch = self.buffer[self.next_char - self.buffer_range.start];
+        self.next_char += 1;
+        Some(ch)
+    }
 }
 
 // Most strings will print only a single line.
@@ -64,6 +139,7 @@ impl<'a> ArgsLineDimensionsIterator<'a> {
     ) -> Result<(), LookupError> {
         let mut line_dimensions = HorizontalRenderedDimensions::empty();
         let mut line_num: usize = 0;
+        let mut previous_char = None;
 
         FormatArgsReader::new(|ch| -> Result<bool, LookupError> {
             if ch == '\n' {
@@ -78,6 +154,7 @@ impl<'a> ArgsLineDimensionsIterator<'a> {
                 }
 
                 line_num += 1;
+                previous_char = None;
 
                 if line_num >= range_start + self.dimensions_buffer.len() {
                     // break if we are past the desired range
@@ -85,6 +162,7 @@ impl<'a> ArgsLineDimensionsIterator<'a> {
                 }
             } else if line_num >= range_start {
                 // Only compute dimensions if we are in a line that will be buffered
+                line_dimensions.advance += font.kerning(previous_char.replace(ch), ch);
                 let dimensions =
                     compute_horizontal_glyph_dimensions(ch, line_dimensions.advance, font)?;
                 line_dimensions.add(dimensions);
@@ -183,6 +261,32 @@ mod tests {
         assert_eq!(format_args!("{}", "").get_newline_count(), 0);
     }
 
+    #[test]
+    fn char_iterator_produces_correct_values() {
+        // Nested function to deal with format_args!()'s weird lifetimes
+        fn run_test(args: Arguments<'_>, expected: &str) {
+            assert_eq!(
+                args.char_iterator().collect::<std::string::String>(),
+                expected
+            );
+        }
+
+        run_test(format_args!(""), "");
+        run_test(format_args!("{}", "abc"), "abc");
+
+        // Longer than the buffer, and exactly as long as it
+        let long = "0123456789abcdefghijklmnopqrstuvwxyz0123456789";
+        run_test(format_args!("{}{}", long, "!"), &std::format!("{long}!"));
+        run_test(
+            format_args!("{}", &long[..NUM_BUFFERED_CHARS]),
+            &long[..NUM_BUFFERED_CHARS],
+        );
+        run_test(
+            format_args!("{}", &long[..2 * NUM_BUFFERED_CHARS]),
+            &long[..2 * NUM_BUFFERED_CHARS],
+        );
+    }
+
     #[test]
     fn line_dimensions_iter_provides_correct_values() {
         // Nested function to deal with format_args!()'s weird lifetimes
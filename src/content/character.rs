@@ -29,6 +29,12 @@ impl Content for char {
     fn line_dimensions_iterator(&self) -> CharLineDimensionsIterator {
         CharLineDimensionsIterator { ch: Some(*self) }
     }
+
+    type CharIter = core::iter::Once<char>;
+
+    fn char_iterator(&self) -> Self::CharIter {
+        core::iter::once(*self)
+    }
 }
 
 pub struct CharLineDimensionsIterator {
@@ -6,7 +6,9 @@ use crate::{
 mod args;
 mod character;
 mod text;
+pub mod truncated_line;
 pub mod vertical_offset;
+pub mod wrapped_lines;
 
 pub trait LineDimensionsIterator {
     fn next(&mut self, font: &FontReader) -> Result<HorizontalRenderedDimensions, LookupError>;
@@ -47,4 +49,10 @@ pub trait Content {
 
     #[doc(hidden)]
     fn line_dimensions_iterator(&self) -> Self::LineDimensionsIter;
+
+    #[doc(hidden)]
+    type CharIter: Iterator<Item = char>;
+
+    #[doc(hidden)]
+    fn char_iterator(&self) -> Self::CharIter;
 }
@@ -29,6 +29,12 @@ impl<'a> Content for &'a str {
     fn line_dimensions_iterator(&self) -> TextLineDimensionsIterator<'a> {
         TextLineDimensionsIterator { data: self.lines() }
     }
+
+    type CharIter = core::str::Chars<'a>;
+
+    fn char_iterator(&self) -> Self::CharIter {
+        self.chars()
+    }
 }
 
 pub struct TextLineDimensionsIterator<'a> {
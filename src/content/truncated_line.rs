@@ -0,0 +1,246 @@
+use crate::{
+    font_reader::FontReader, renderer::render_actions::compute_horizontal_glyph_dimensions,
+    utils::HorizontalRenderedDimensions, Content, LookupError,
+};
+
+/// How the first line of a text gets shortened to fit into a maximum width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Truncation {
+    /// The number of characters that get rendered.
+    pub visible_chars: usize,
+    /// Whether the text got shortened.
+    pub truncated: bool,
+    /// The ellipsis to render after the visible characters.
+    ///
+    /// Empty if the text didn't get shortened, or if not even the ellipsis fits.
+    pub ellipsis: &'static str,
+    /// The dimensions of the rendered line, including the ellipsis.
+    pub dimensions: HorizontalRenderedDimensions,
+}
+
+enum TruncationError {
+    Lookup(LookupError),
+    EndOfLine,
+}
+
+impl From<LookupError> for TruncationError {
+    fn from(e: LookupError) -> Self {
+        Self::Lookup(e)
+    }
+}
+
+/// Measures a string that gets appended to a line, relative to its start.
+fn measure_str(text: &str, font: &FontReader) -> Result<HorizontalRenderedDimensions, LookupError> {
+    let mut dimensions = HorizontalRenderedDimensions::empty();
+    let mut previous_char = None;
+    for ch in text.chars() {
+        dimensions.advance += font.kerning(previous_char.replace(ch), ch);
+        dimensions.append_glyph(&compute_horizontal_glyph_dimensions(ch, 0, font)?);
+    }
+    Ok(dimensions)
+}
+
+/// Lays out the first line of the content, until `stop` returns `true` for the line
+/// including the latest character.
+///
+/// Returns the number of laid out characters, the line, and whether the line
+/// ended at a newline character.
+fn measure_first_line(
+    content: &impl Content,
+    font: &FontReader,
+    mut stop: impl FnMut(&HorizontalRenderedDimensions, char) -> bool,
+) -> Result<(usize, HorizontalRenderedDimensions, bool), LookupError> {
+    let mut line = HorizontalRenderedDimensions::empty();
+    let mut previous_char = None;
+    let mut char_count = 0;
+    let mut has_newline = false;
+
+    let result = content.for_each_char(|ch| {
+        if ch == '\n' {
+            has_newline = true;
+            return Err(TruncationError::EndOfLine);
+        }
+
+        line.advance += font.kerning(previous_char.replace(ch), ch);
+        line.append_glyph(&compute_horizontal_glyph_dimensions(ch, 0, font)?);
+        char_count += 1;
+
+        if stop(&line, ch) {
+            return Err(TruncationError::EndOfLine);
+        }
+
+        Ok(())
+    });
+
+    match result {
+        Ok(()) | Err(TruncationError::EndOfLine) => Ok((char_count, line, has_newline)),
+        Err(TruncationError::Lookup(e)) => Err(e),
+    }
+}
+
+/// Finds out how much of the first line of the content fits into `max_width`.
+///
+/// If the line doesn't fit, it gets cut after the last character that
+/// still fits together with an ellipsis. Newline characters count as truncation,
+/// as everything after them can't be shown in a single line.
+/// Fonts that can render neither `…` nor `.` get truncated without an ellipsis.
+pub fn compute_truncation(
+    content: &impl Content,
+    max_width: i32,
+    font: &FontReader,
+) -> Result<Truncation, LookupError> {
+    // Most texts fit, so the ellipsis only gets measured once the line overflows
+    let (char_count, line, has_newline) =
+        measure_first_line(content, font, |line, _| line.width() > max_width)?;
+
+    if !has_newline && line.width() <= max_width {
+        return Ok(Truncation {
+            visible_chars: char_count,
+            truncated: false,
+            ellipsis: "",
+            dimensions: line,
+        });
+    }
+
+    let ellipsis_str = if font.has_glyph('…') {
+        "…"
+    } else if font.has_glyph('.') {
+        "..."
+    } else {
+        ""
+    };
+    let ellipsis = measure_str(ellipsis_str, font)?;
+    let ellipsis_start = ellipsis_str.chars().next();
+
+    // The line up to the last character that fits, including the ellipsis
+    let mut shortened_line = ellipsis.clone();
+    let mut overflows = false;
+    let (char_count, _, _) = measure_first_line(content, font, |line, ch| {
+        let mut shortened = line.clone();
+        shortened.advance += ellipsis_start.map_or(0, |e| font.kerning(Some(ch), e));
+        shortened.append_glyph(&ellipsis);
+        overflows = shortened.width() > max_width;
+        if !overflows {
+            shortened_line = shortened;
+        }
+        overflows
+    })?;
+
+    let visible_chars = if overflows {
+        char_count - 1
+    } else {
+        char_count
+    };
+    let ellipsis_fits = visible_chars > 0 || ellipsis.width() <= max_width;
+
+    Ok(if ellipsis_fits {
+        Truncation {
+            visible_chars,
+            truncated: true,
+            ellipsis: ellipsis_str,
+            dimensions: shortened_line,
+        }
+    } else {
+        Truncation {
+            visible_chars,
+            truncated: true,
+            ellipsis: "",
+            dimensions: HorizontalRenderedDimensions::empty(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fonts;
+
+    use super::*;
+
+    fn truncate(text: &str, max_width: i32) -> (usize, bool, &'static str) {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        let truncation = compute_truncation(&text, max_width, &font).unwrap();
+        (
+            truncation.visible_chars,
+            truncation.truncated,
+            truncation.ellipsis,
+        )
+    }
+
+    #[test]
+    fn keeps_fitting_text() {
+        assert_eq!(truncate("abcd", 15), (4, false, ""));
+        assert_eq!(truncate("", 0), (0, false, ""));
+    }
+
+    #[test]
+    fn cuts_text_before_ellipsis() {
+        // "..." is 5 pixels wide in this font
+        assert_eq!(truncate("abcd", 14), (2, true, "..."));
+        assert_eq!(truncate("abcd", 12), (1, true, "..."));
+        assert_eq!(truncate("abcd", 8), (0, true, "..."));
+    }
+
+    #[test]
+    fn drops_ellipsis_if_it_does_not_fit() {
+        assert_eq!(truncate("abcd", 4), (0, true, ""));
+    }
+
+    #[test]
+    fn measures_truncated_line() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        let expected = |text: &str| {
+            let mut dimensions = HorizontalRenderedDimensions::empty();
+            for ch in text.chars() {
+                dimensions
+                    .append_glyph(&compute_horizontal_glyph_dimensions(ch, 0, &font).unwrap());
+            }
+            dimensions
+        };
+
+        let measure = |text: &str, max_width| {
+            compute_truncation(&text, max_width, &font)
+                .unwrap()
+                .dimensions
+        };
+
+        assert_eq!(measure("abcd", 15), expected("abcd"));
+        assert_eq!(measure("abcd", 14), expected("ab..."));
+        assert_eq!(measure("abcd", 8), expected("..."));
+        assert_eq!(measure("abcd", 4), expected(""));
+        assert_eq!(measure("ab\ncd", 100), expected("ab..."));
+    }
+
+    #[test]
+    fn truncates_at_newlines() {
+        assert_eq!(truncate("ab\ncd", 100), (2, true, "..."));
+    }
+
+    #[test]
+    fn truncates_without_ellipsis_if_font_has_none() {
+        let font = FontReader::new::<fonts::u8g2_font_open_iconic_all_1x_t>();
+        let text = "ABCDEFGH";
+
+        let truncation = compute_truncation(&text, 1000, &font).unwrap();
+        assert_eq!((truncation.visible_chars, truncation.truncated), (8, false));
+
+        // Every glyph of this font is 8 pixels wide
+        let truncation = compute_truncation(&text, 20, &font).unwrap();
+        assert_eq!(
+            (
+                truncation.visible_chars,
+                truncation.truncated,
+                truncation.ellipsis
+            ),
+            (2, true, "")
+        );
+    }
+
+    #[test]
+    fn propagates_lookup_errors() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        assert!(matches!(
+            compute_truncation(&"a☃", 100, &font),
+            Err(LookupError::GlyphNotFound('☃'))
+        ));
+    }
+}
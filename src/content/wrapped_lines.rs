@@ -0,0 +1,560 @@
+use core::{cmp, ops::Range};
+
+use crate::{
+    font_reader::FontReader, renderer::render_actions::compute_horizontal_glyph_dimensions,
+    utils::HorizontalRenderedDimensions, Content, LookupError,
+};
+
+/// A single line of word-wrapped text.
+///
+/// Characters are referenced by their index in the content.
+/// Spaces at which the text got wrapped and newline characters
+/// are not part of any line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedLine {
+    /// The index of the first character of the line.
+    pub start: usize,
+    /// The index after the last character that gets rendered.
+    pub end: usize,
+    /// The dimensions of the rendered characters.
+    pub dimensions: HorizontalRenderedDimensions,
+    /// The number of gaps between words, which can be
+    /// stretched to justify the line.
+    pub gap_count: u32,
+    /// Whether the line ends at a newline character or the end of the content,
+    /// instead of getting wrapped.
+    pub paragraph_end: bool,
+}
+
+impl WrappedLine {
+    pub fn empty(start: usize) -> Self {
+        Self {
+            start,
+            end: start,
+            dimensions: HorizontalRenderedDimensions::empty(),
+            gap_count: 0,
+            paragraph_end: true,
+        }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        (self.start..self.end).contains(&index)
+    }
+
+    /// The additional space that has to be inserted into
+    /// the gap with the given index to justify the line to `width`.
+    pub fn justify_gap_spacing(&self, gap: u32, width: i32) -> i32 {
+        if self.paragraph_end || self.gap_count == 0 {
+            return 0;
+        }
+
+        let extra_space = cmp::max(width - self.dimensions.width(), 0);
+        let gap_count = i32::try_from(self.gap_count).unwrap_or(i32::MAX);
+        let gap = i32::try_from(gap).unwrap_or(i32::MAX);
+
+        // Distribute the remainder over the first gaps
+        extra_space / gap_count + i32::from(gap < extra_space % gap_count)
+    }
+}
+
+/// Splits a stream of characters into lines that fit into `max_width`.
+///
+/// Lines get wrapped at spaces; a single word that is too long for a line gets
+/// wrapped between its characters instead. A line always contains at least one
+/// glyph, so glyphs that are wider than `max_width` still get rendered.
+struct LineWrapper {
+    max_width: i32,
+    line_start: usize,
+    /// Everything in the current line, including trailing spaces.
+    line: HorizontalRenderedDimensions,
+    /// The current line without its trailing spaces.
+    content_end: usize,
+    content: HorizontalRenderedDimensions,
+    content_gaps: u32,
+    /// The current line up to the last complete word, where it could be wrapped.
+    break_point: Option<(usize, HorizontalRenderedDimensions, u32)>,
+    /// The current word, relative to its start.
+    word_start: usize,
+    word: HorizontalRenderedDimensions,
+    in_word: bool,
+    /// The last character of the current line, for kerning.
+    previous_char: Option<char>,
+}
+
+impl LineWrapper {
+    fn new(max_width: i32) -> Self {
+        Self {
+            max_width,
+            line_start: 0,
+            line: HorizontalRenderedDimensions::empty(),
+            content_end: 0,
+            content: HorizontalRenderedDimensions::empty(),
+            content_gaps: 0,
+            break_point: None,
+            word_start: 0,
+            word: HorizontalRenderedDimensions::empty(),
+            in_word: false,
+            previous_char: None,
+        }
+    }
+
+    fn start_line(&mut self, start: usize, dimensions: HorizontalRenderedDimensions) {
+        self.line_start = start;
+        self.line = dimensions.clone();
+        self.content_end = start;
+        self.content = dimensions;
+        self.content_gaps = 0;
+        self.break_point = None;
+    }
+
+    fn emit_line(
+        &self,
+        end: usize,
+        dimensions: HorizontalRenderedDimensions,
+        gap_count: u32,
+        paragraph_end: bool,
+    ) -> WrappedLine {
+        WrappedLine {
+            start: self.line_start,
+            end,
+            dimensions,
+            gap_count,
+            paragraph_end,
+        }
+    }
+
+    fn emit_content(&self, paragraph_end: bool) -> WrappedLine {
+        self.emit_line(
+            self.content_end,
+            self.content.clone(),
+            self.content_gaps,
+            paragraph_end,
+        )
+    }
+
+    fn push(
+        &mut self,
+        index: usize,
+        ch: char,
+        font: &FontReader,
+        mut emit: impl FnMut(WrappedLine),
+    ) -> Result<(), LookupError> {
+        match ch {
+            '\n' => {
+                emit(self.emit_content(true));
+                self.start_line(index + 1, HorizontalRenderedDimensions::empty());
+                self.in_word = false;
+                self.previous_char = None;
+            }
+            ' ' => {
+                if self.in_word {
+                    self.in_word = false;
+                    self.break_point =
+                        Some((self.content_end, self.content.clone(), self.content_gaps));
+                }
+                let glyph = compute_horizontal_glyph_dimensions(ch, 0, font)?;
+                self.line.advance += font.kerning(self.previous_char.replace(ch), ch);
+                self.line.append_glyph(&glyph);
+            }
+            _ => {
+                if !self.in_word {
+                    self.in_word = true;
+                    self.word_start = index;
+                    self.word = HorizontalRenderedDimensions::empty();
+                }
+
+                let glyph = compute_horizontal_glyph_dimensions(ch, 0, font)?;
+
+                loop {
+                    let kerning = font.kerning(self.previous_char, ch);
+                    let mut line = self.line.clone();
+                    line.advance += kerning;
+                    line.append_glyph(&glyph);
+
+                    let line_is_empty = self.content_end == self.line_start;
+                    if line_is_empty || line.width() <= self.max_width {
+                        if index == self.word_start && !line_is_empty {
+                            // A word that follows a previous word in the same line
+                            self.content_gaps += 1;
+                        }
+                        self.line = line;
+                        if index != self.word_start {
+                            self.word.advance += kerning;
+                        }
+                        self.word.append_glyph(&glyph);
+                        self.content_end = index + 1;
+                        self.content = self.line.clone();
+                        self.previous_char = Some(ch);
+                        break;
+                    }
+
+                    if let Some((end, dimensions, gap_count)) = self.break_point.take() {
+                        // Wrap before the current word
+                        emit(self.emit_line(end, dimensions, gap_count, false));
+                        self.start_line(self.word_start, self.word.clone());
+                        self.content_end = index;
+                        if index == self.word_start {
+                            self.previous_char = None;
+                        }
+                    } else {
+                        // The word doesn't fit into a line on its own; wrap inside of it
+                        emit(self.emit_content(false));
+                        self.start_line(index, HorizontalRenderedDimensions::empty());
+                        self.word_start = index;
+                        self.word = HorizontalRenderedDimensions::empty();
+                        self.previous_char = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self, mut emit: impl FnMut(WrappedLine)) {
+        emit(self.emit_content(true));
+    }
+}
+
+enum WrapError {
+    Lookup(LookupError),
+    BufferFull,
+}
+
+impl From<LookupError> for WrapError {
+    fn from(e: LookupError) -> Self {
+        Self::Lookup(e)
+    }
+}
+
+/// Counts the lines that word-wrapping the content into `max_width` produces.
+pub fn count_wrapped_lines(
+    content: &impl Content,
+    max_width: i32,
+    font: &FontReader,
+) -> Result<usize, LookupError> {
+    let mut wrapper = LineWrapper::new(max_width);
+    let mut line_count = 0;
+    let mut index = 0;
+
+    content.for_each_char(|ch| {
+        wrapper.push(index, ch, font, |_| line_count += 1)?;
+        index += 1;
+        Ok::<(), LookupError>(())
+    })?;
+    wrapper.finish(|_| line_count += 1);
+
+    Ok(line_count)
+}
+
+// Same reasoning as for `ArgsLineDimensionsIterator`:
+// most texts only wrap into a couple of lines.
+const NUM_BUFFERED_LINES: usize = 5;
+
+/// Iterates over the word-wrapped lines of a content.
+///
+/// Works without allocation by re-running the line wrapping whenever
+/// lines are requested that aren't buffered yet.
+pub struct WrappedLinesIterator<'c, C> {
+    content: &'c C,
+    max_width: i32,
+    buffer_range: Range<usize>,
+    lines_buffer: [WrappedLine; NUM_BUFFERED_LINES],
+    next_line: usize,
+    finished: bool,
+}
+
+impl<'c, C: Content> WrappedLinesIterator<'c, C> {
+    pub fn new(content: &'c C, max_width: i32) -> Self {
+        Self {
+            content,
+            max_width,
+            buffer_range: 0..0,
+            lines_buffer: [(); NUM_BUFFERED_LINES].map(|()| WrappedLine::empty(0)),
+            next_line: 0,
+            finished: false,
+        }
+    }
+
+    pub fn regenerate_buffer(
+        &mut self,
+        range_start: usize,
+        font: &FontReader,
+    ) -> Result<(), LookupError> {
+        let mut wrapper = LineWrapper::new(self.max_width);
+        let mut sink = LineSink {
+            range_start,
+            line_num: 0,
+            buffer: &mut self.lines_buffer,
+        };
+        let mut index = 0;
+
+        let result = self.content.for_each_char(|ch| {
+            wrapper.push(index, ch, font, |line| sink.store(line))?;
+            index += 1;
+
+            if sink.is_full() {
+                // break if we are past the desired range
+                Err(WrapError::BufferFull)
+            } else {
+                Ok(())
+            }
+        });
+
+        match result {
+            Ok(()) => {
+                // The content ran out, so the last line is complete as well
+                wrapper.finish(|line| sink.store(line));
+                self.finished = true;
+            }
+            Err(WrapError::BufferFull) => (),
+            Err(WrapError::Lookup(e)) => return Err(e),
+        }
+
+        self.buffer_range = range_start..cmp::max(range_start, sink.line_num);
+        if self.buffer_range.len() > NUM_BUFFERED_LINES {
+            self.buffer_range.end = range_start + NUM_BUFFERED_LINES;
+            self.finished = false;
+        }
+
+        Ok(())
+    }
+
+    pub fn next(&mut self, font: &FontReader) -> Result<Option<WrappedLine>, LookupError> {
+        let next_line = self.next_line;
+
+        if !self.buffer_range.contains(&next_line) {
+            if self.finished {
+                return Ok(None);
+            }
+
+            self.regenerate_buffer(next_line, font)?;
+            if !self.buffer_range.contains(&next_line) {
+                return Ok(None);
+            }
+        }
+
+        self.next_line += 1;
+        Ok(Some(
+            self.lines_buffer[next_line - self.buffer_range.start].clone(),
+        ))
+    }
+}
+
+struct LineSink<'b> {
+    range_start: usize,
+    line_num: usize,
+    buffer: &'b mut [WrappedLine],
+}
+
+impl LineSink<'_> {
+    fn store(&mut self, line: WrappedLine) {
+        if let Some(array_pos) = self.line_num.checked_sub(self.range_start) {
+            if let Some(cell) = self.buffer.get_mut(array_pos) {
+                // If we are in the correct range, set the value in the array
+                *cell = line;
+            }
+        }
+        self.line_num += 1;
+    }
+
+    fn is_full(&self) -> bool {
+        self.line_num >= self.range_start + self.buffer.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use core::fmt::Arguments;
+    use std::vec::Vec;
+
+    use crate::{fonts, types::KerningPair};
+
+    use super::*;
+
+    fn wrap(text: &str, max_width: i32) -> Vec<(usize, usize)> {
+        wrap_with_font(
+            &FontReader::new::<fonts::u8g2_font_u8glib_4_tf>(),
+            text,
+            max_width,
+        )
+    }
+
+    fn wrap_with_font(font: &FontReader, text: &str, max_width: i32) -> Vec<(usize, usize)> {
+        let mut wrapper = LineWrapper::new(max_width);
+        let mut lines = Vec::new();
+
+        for (index, ch) in text.chars().enumerate() {
+            wrapper
+                .push(index, ch, font, |line| lines.push((line.start, line.end)))
+                .unwrap();
+        }
+        wrapper.finish(|line| lines.push((line.start, line.end)));
+
+        lines
+    }
+
+    #[test]
+    fn keeps_fitting_text_in_one_line() {
+        assert_eq!(wrap("ab cd", 17), [(0, 5)]);
+    }
+
+    #[test]
+    fn wraps_at_spaces() {
+        assert_eq!(wrap("ab cd", 16), [(0, 2), (3, 5)]);
+        assert_eq!(wrap("ab   cd", 10), [(0, 2), (5, 7)]);
+    }
+
+    #[test]
+    fn wraps_inside_of_long_words() {
+        assert_eq!(wrap("abcd", 8), [(0, 2), (2, 4)]);
+        assert_eq!(wrap("ab cdab", 8), [(0, 2), (3, 5), (5, 7)]);
+    }
+
+    #[test]
+    fn keeps_at_least_one_glyph_per_line() {
+        assert_eq!(wrap("ab", 0), [(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn wraps_at_newlines() {
+        assert_eq!(wrap("ab\n\ncd", 100), [(0, 2), (3, 3), (4, 6)]);
+        assert_eq!(wrap("ab\n", 100), [(0, 2), (3, 3)]);
+        assert_eq!(wrap("", 100), [(0, 0)]);
+    }
+
+    #[test]
+    fn keeps_leading_and_drops_trailing_spaces() {
+        assert_eq!(wrap("  ab  ", 100), [(0, 4)]);
+        assert_eq!(wrap("ab  \n  cd", 100), [(0, 2), (5, 9)]);
+    }
+
+    #[test]
+    fn applies_kerning() {
+        let kerning = [
+            KerningPair::new(' ', 'c', -3),
+            KerningPair::new('a', 'b', -1),
+            KerningPair::new('b', 'c', -2),
+        ];
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>().with_kerning(&kerning);
+
+        assert_eq!(wrap("abcd", 8), [(0, 2), (2, 4)]);
+        assert_eq!(wrap_with_font(&font, "abcd", 8), [(0, 3), (3, 4)]);
+
+        assert_eq!(wrap("ab cd", 16), [(0, 2), (3, 5)]);
+        assert_eq!(wrap_with_font(&font, "ab cd", 16), [(0, 5)]);
+    }
+
+    #[test]
+    fn applies_spacing() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>().with_letter_spacing(1);
+        assert_eq!(wrap("abcd", 15), [(0, 4)]);
+        assert_eq!(wrap_with_font(&font, "abcd", 15), [(0, 3), (3, 4)]);
+
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>().with_word_spacing(1);
+        assert_eq!(wrap("ab cd", 17), [(0, 5)]);
+        assert_eq!(wrap_with_font(&font, "ab cd", 17), [(0, 2), (3, 5)]);
+    }
+
+    #[test]
+    fn counts_gaps_between_words() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        let mut lines = WrappedLinesIterator::new(&"  ab  c d ab\nabcd", 26);
+
+        let first = lines.next(&font).unwrap().unwrap();
+        assert_eq!((first.start, first.end), (0, 9));
+        assert_eq!(first.gap_count, 2);
+        assert!(!first.paragraph_end);
+
+        let second = lines.next(&font).unwrap().unwrap();
+        assert_eq!((second.start, second.end), (10, 12));
+        assert_eq!(second.gap_count, 0);
+        assert!(second.paragraph_end);
+    }
+
+    #[test]
+    fn distributes_justify_spacing_over_gaps() {
+        let line = WrappedLine {
+            start: 0,
+            end: 5,
+            dimensions: HorizontalRenderedDimensions {
+                advance: 10,
+                bounding_box_width: 9,
+                bounding_box_offset: 0,
+            },
+            gap_count: 3,
+            paragraph_end: false,
+        };
+
+        assert_eq!(line.justify_gap_spacing(0, 15), 2);
+        assert_eq!(line.justify_gap_spacing(1, 15), 2);
+        assert_eq!(line.justify_gap_spacing(2, 15), 1);
+
+        let last_line = WrappedLine {
+            paragraph_end: true,
+            ..line
+        };
+        assert_eq!(last_line.justify_gap_spacing(0, 15), 0);
+    }
+
+    #[test]
+    fn reports_line_dimensions() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        let mut lines = WrappedLinesIterator::new(&"ab cd  ", 100);
+
+        assert_eq!(
+            lines.next(&font).unwrap(),
+            Some(WrappedLine {
+                start: 0,
+                end: 5,
+                dimensions: HorizontalRenderedDimensions {
+                    advance: 17,
+                    bounding_box_width: 16,
+                    bounding_box_offset: 0,
+                },
+                gap_count: 1,
+                paragraph_end: true,
+            })
+        );
+        assert_eq!(lines.next(&font).unwrap(), None);
+    }
+
+    #[test]
+    fn iterator_matches_wrapper_beyond_buffer() {
+        // Nested function to deal with format_args!()'s weird lifetimes
+        fn run_test(args: Arguments<'_>) {
+            let text = "a b c d e f g h i j k l";
+            let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+
+            let expected = wrap(text, 4);
+            assert_eq!(expected.len(), 12);
+            assert_eq!(count_wrapped_lines(&text, 4, &font).unwrap(), 12);
+            assert_eq!(count_wrapped_lines(&args, 4, &font).unwrap(), 12);
+
+            let mut str_lines = WrappedLinesIterator::new(&text, 4);
+            let mut args_lines = WrappedLinesIterator::new(&args, 4);
+            for expected_line in expected {
+                let str_line = str_lines.next(&font).unwrap().unwrap();
+                let args_line = args_lines.next(&font).unwrap().unwrap();
+                assert_eq!((str_line.start, str_line.end), expected_line);
+                assert_eq!(args_line, str_line);
+            }
+            assert_eq!(str_lines.next(&font).unwrap(), None);
+            assert_eq!(args_lines.next(&font).unwrap(), None);
+        }
+
+        run_test(format_args!("{}", "a b c d e f g h i j k l"));
+    }
+
+    #[test]
+    fn iterator_errors_on_glyph_not_found() {
+        let font = FontReader::new::<fonts::u8g2_font_u8glib_4_tf>();
+        let mut lines = WrappedLinesIterator::new(&"a ☃", 100);
+
+        assert!(matches!(
+            lines.next(&font),
+            Err(LookupError::GlyphNotFound('☃'))
+        ));
+    }
+}
@@ -7,6 +7,49 @@ pub enum LookupError {
     GlyphNotFound(char),
 }
 
+/// All possible errors that can be detected while validating font data.
+///
+/// Returned by [`FontRenderer::try_from_data()`](crate::FontRenderer::try_from_data).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontDataError {
+    /// The data is too short for a font header or the header contains invalid values.
+    InvalidHeader,
+    /// The glyph table is truncated or its offsets are inconsistent.
+    InvalidGlyphTable,
+    /// The unicode jump table is truncated or points to invalid locations.
+    InvalidUnicodeTable,
+}
+
+/// All possible errors rendering to a bitmap can cause.
+///
+/// Returned by [`FontRenderer::render_to_bitmap()`](crate::FontRenderer::render_to_bitmap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapError {
+    /// Font does not contain given character.
+    GlyphNotFound(char),
+    /// The stride is too small for the width of the rendered text.
+    StrideTooSmall {
+        /// The minimum stride, in bytes.
+        required: usize,
+    },
+    /// The buffer is too small for the rendered text.
+    BufferTooSmall {
+        /// The minimum length of the buffer, in bytes.
+        required: usize,
+    },
+}
+
+/// All possible errors preparing text can cause.
+///
+/// Returned by [`FontRenderer::prepare()`](crate::FontRenderer::prepare).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrepareError {
+    /// Font does not contain given character.
+    GlyphNotFound(char),
+    /// The text consists of more glyphs than the prepared text can hold.
+    TooManyGlyphs,
+}
+
 /// All possible errors a draw call can cause.
 #[derive(Debug)]
 pub enum Error<DisplayError> {
@@ -18,6 +61,18 @@ pub enum Error<DisplayError> {
     DisplayError(DisplayError),
 }
 
+/// The errors that can occur while drawing text that already passed the checks of a draw call.
+///
+/// Unlike [`Error`], it can't describe an unsupported background color,
+/// so infallible displays make the whole drawing infallible, except for missing glyphs.
+#[derive(Debug)]
+pub enum DrawError<DisplayError> {
+    /// Font does not contain given character.
+    GlyphNotFound(char),
+    /// Writing to display failed.
+    DisplayError(DisplayError),
+}
+
 impl<DisplayError> Display for Error<DisplayError>
 where
     DisplayError: Display + Debug,
@@ -45,6 +100,74 @@ impl Display for LookupError {
     }
 }
 
+impl Display for FontDataError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            FontDataError::InvalidHeader => write!(f, "The font header is invalid."),
+            FontDataError::InvalidGlyphTable => {
+                write!(f, "The glyph table of the font is corrupt.")
+            }
+            FontDataError::InvalidUnicodeTable => {
+                write!(f, "The unicode jump table of the font is corrupt.")
+            }
+        }
+    }
+}
+
+impl Display for BitmapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            BitmapError::GlyphNotFound(c) => {
+                write!(f, "This font does not support the character '{c}'.")
+            }
+            BitmapError::StrideTooSmall { required } => {
+                write!(
+                    f,
+                    "The stride is too small, it has to be at least {required}."
+                )
+            }
+            BitmapError::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "The buffer is too small, it has to be at least {required} bytes long."
+                )
+            }
+        }
+    }
+}
+
+impl Display for PrepareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            PrepareError::GlyphNotFound(c) => {
+                write!(f, "This font does not support the character '{c}'.")
+            }
+            PrepareError::TooManyGlyphs => {
+                write!(
+                    f,
+                    "The text has more glyphs than the prepared text can hold."
+                )
+            }
+        }
+    }
+}
+
+impl From<LookupError> for PrepareError {
+    fn from(e: LookupError) -> Self {
+        match e {
+            LookupError::GlyphNotFound(g) => PrepareError::GlyphNotFound(g),
+        }
+    }
+}
+
+impl From<LookupError> for BitmapError {
+    fn from(e: LookupError) -> Self {
+        match e {
+            LookupError::GlyphNotFound(g) => BitmapError::GlyphNotFound(g),
+        }
+    }
+}
+
 impl<T> From<LookupError> for Error<T> {
     fn from(e: LookupError) -> Self {
         match e {
@@ -53,8 +176,31 @@ impl<T> From<LookupError> for Error<T> {
     }
 }
 
+impl<T> From<LookupError> for DrawError<T> {
+    fn from(e: LookupError) -> Self {
+        match e {
+            LookupError::GlyphNotFound(g) => DrawError::GlyphNotFound(g),
+        }
+    }
+}
+
+impl<T> From<DrawError<T>> for Error<T> {
+    fn from(e: DrawError<T>) -> Self {
+        match e {
+            DrawError::GlyphNotFound(g) => Error::GlyphNotFound(g),
+            DrawError::DisplayError(e) => Error::DisplayError(e),
+        }
+    }
+}
+
 impl core::error::Error for LookupError {}
 
+impl core::error::Error for FontDataError {}
+
+impl core::error::Error for BitmapError {}
+
+impl core::error::Error for PrepareError {}
+
 impl<DisplayError> core::error::Error for Error<DisplayError> where
     DisplayError: core::fmt::Debug + core::fmt::Display
 {
@@ -90,5 +236,34 @@ mod tests {
             Error::<&'static str>::DisplayError("This is a display error!"),
             "Writing to display failed: This is a display error!",
         );
+        examine_error(FontDataError::InvalidHeader, "The font header is invalid.");
+        examine_error(
+            FontDataError::InvalidGlyphTable,
+            "The glyph table of the font is corrupt.",
+        );
+        examine_error(
+            FontDataError::InvalidUnicodeTable,
+            "The unicode jump table of the font is corrupt.",
+        );
+        examine_error(
+            BitmapError::GlyphNotFound('c'),
+            "This font does not support the character 'c'.",
+        );
+        examine_error(
+            BitmapError::StrideTooSmall { required: 3 },
+            "The stride is too small, it has to be at least 3.",
+        );
+        examine_error(
+            BitmapError::BufferTooSmall { required: 42 },
+            "The buffer is too small, it has to be at least 42 bytes long.",
+        );
+        examine_error(
+            PrepareError::GlyphNotFound('d'),
+            "This font does not support the character 'd'.",
+        );
+        examine_error(
+            PrepareError::TooManyGlyphs,
+            "The text has more glyphs than the prepared text can hold.",
+        );
     }
 }
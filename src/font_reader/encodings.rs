@@ -0,0 +1,96 @@
+use crate::font_reader::{glyph_searcher::GlyphSearcher, FontReader};
+
+/// An iterator over all characters a font contains.
+///
+/// Created by [`FontRenderer::encodings()`](crate::FontRenderer::encodings).
+pub struct Encodings<'f, 'a> {
+    font: &'f FontReader<'a>,
+    state: EncodingsState<'f, 'a>,
+}
+
+enum EncodingsState<'f, 'a> {
+    Ascii(GlyphSearcher<'a, 'f, 1>),
+    Unicode(GlyphSearcher<'a, 'f, 2>),
+    Done,
+}
+
+impl<'f, 'a> Encodings<'f, 'a> {
+    pub(crate) fn new(font: &'f FontReader<'a>) -> Self {
+        Self {
+            font,
+            state: EncodingsState::Ascii(GlyphSearcher::new(font)),
+        }
+    }
+
+    fn enter_unicode_section(&mut self) {
+        let (mut glyph, unicode_jump_table) =
+            GlyphSearcher::new(self.font).into_unicode_mode(self.font.array_offset_0x0100);
+
+        // The first entry of the jump table leads to the first glyph
+        self.state = match unicode_jump_table.calculate_jump_offset(0) {
+            Some(offset) => {
+                glyph.jump_by(offset);
+                EncodingsState::Unicode(glyph)
+            }
+            None => EncodingsState::Done,
+        };
+    }
+}
+
+impl Iterator for Encodings<'_, '_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            match &mut self.state {
+                EncodingsState::Ascii(glyph) => match glyph.get_ch() {
+                    Some(ch) if !glyph.is_at_end() => {
+                        glyph.jump_to_next();
+                        return Some(char::from(ch));
+                    }
+                    _ => self.enter_unicode_section(),
+                },
+                EncodingsState::Unicode(glyph) => match glyph.get_ch() {
+                    Some(encoding) if encoding != 0 && !glyph.is_at_end() => {
+                        glyph.jump_to_next();
+                        // Surrogates are no characters, no font should contain them
+                        if let Some(ch) = char::from_u32(encoding.into()) {
+                            return Some(ch);
+                        }
+                    }
+                    _ => self.state = EncodingsState::Done,
+                },
+                EncodingsState::Done => return None,
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Encodings<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::vec::Vec;
+
+    use crate::fonts;
+
+    use super::*;
+
+    #[test]
+    fn lists_all_glyphs() {
+        let font = FontReader::new::<fonts::u8g2_font_helvR08_tr>();
+        let encodings = Encodings::new(&font).collect::<Vec<_>>();
+        assert_eq!(encodings, (' '..='~').collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn lists_unicode_glyphs() {
+        let font = FontReader::new::<fonts::u8g2_font_unifont_t_symbols>();
+        let encodings = Encodings::new(&font).collect::<Vec<_>>();
+        assert!(encodings.contains(&'A'));
+        assert!(encodings.contains(&'☃'));
+        assert!(encodings.iter().all(|&ch| font.has_glyph(ch)));
+        assert!(encodings.windows(2).all(|w| w[0] < w[1]));
+    }
+}
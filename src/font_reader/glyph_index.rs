@@ -0,0 +1,401 @@
+use crate::{font_reader::U8G2_FONT_DATA_STRUCT_SIZE, utils::DebugIgnore, Font};
+
+/// A lookup index that speeds up the search for unicode glyphs in large fonts.
+///
+/// Without an index, every character above `0xff` gets searched by walking the
+/// glyph list, only shortened by the coarse unicode jump table of the font. For large
+/// fonts like [`u8g2_font_wqy12_t_gb2312`](crate::fonts::u8g2_font_wqy12_t_gb2312) or
+/// [`u8g2_font_unifont_t_chinese1`](crate::fonts::u8g2_font_unifont_t_chinese1),
+/// this can take thousands of steps per character.
+///
+/// The index samples up to `N` glyphs of the font, evenly spaced. A lookup performs
+/// a binary search on those samples and walks the remaining glyphs from there on. If `N`
+/// is at least the number of unicode glyphs in the font, every lookup is a pure binary search;
+/// smaller indices trade lookup speed for memory. Every sample takes 8 bytes.
+///
+/// The index gets attached to a renderer through [`FontRenderer::with_glyph_index()`](crate::FontRenderer::with_glyph_index).
+/// As its construction is `const`, it can be precomputed at compile time and stored in flash:
+///
+/// ```rust
+/// # use u8g2_fonts::{fonts, FontRenderer, GlyphIndex};
+/// static INDEX: GlyphIndex<512> = GlyphIndex::new::<fonts::u8g2_font_wqy12_t_gb2312>();
+/// static FONT: FontRenderer =
+///     FontRenderer::new::<fonts::u8g2_font_wqy12_t_gb2312>().with_glyph_index(&INDEX);
+/// ```
+///
+/// Lookups through the index produce exactly the same results as lookups without it.
+/// Fonts whose glyph list is not sorted in the way the unicode jump table expects
+/// can't be indexed; for those, the index is silently ignored.
+#[derive(Debug, Clone)]
+pub struct GlyphIndex<const N: usize> {
+    entries: [GlyphIndexEntry; N],
+    len: usize,
+    font_size: usize,
+    /// Copy of the font header, to recognize the indexed font
+    font_header: [u8; U8G2_FONT_DATA_STRUCT_SIZE],
+    usable: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GlyphIndexEntry {
+    encoding: u16,
+    /// Offset of the glyph, relative to the start of the unicode section
+    offset: u32,
+}
+
+/// The part of a [`GlyphIndex`] that is relevant for lookups.
+#[derive(Debug, Clone)]
+pub struct GlyphIndexRef<'a> {
+    entries: DebugIgnore<&'a [GlyphIndexEntry]>,
+}
+
+/// The position of the unicode glyphs inside of the font data.
+struct UnicodeGlyphs {
+    section_start: usize,
+    glyphs_start: usize,
+    count: usize,
+}
+
+impl<const N: usize> GlyphIndex<N> {
+    /// Creates the glyph index of a built-in font.
+    ///
+    /// # Generics
+    ///
+    /// * `FONT` - the font to index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub const fn new<FONT: Font>() -> Self {
+        Self::from_data(FONT::DATA)
+    }
+
+    /// Creates the glyph index of raw font data.
+    ///
+    /// Corrupt font data produces an index that gets ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw font data, as passed to [`FontRenderer::from_data()`](crate::FontRenderer::from_data).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub const fn from_data(data: &[u8]) -> Self {
+        assert!(N > 0, "A glyph index needs at least one entry");
+
+        let mut index = Self {
+            entries: [GlyphIndexEntry {
+                encoding: 0,
+                offset: 0,
+            }; N],
+            len: 0,
+            font_size: data.len(),
+            font_header: [0; U8G2_FONT_DATA_STRUCT_SIZE],
+            usable: false,
+        };
+
+        if data.len() >= U8G2_FONT_DATA_STRUCT_SIZE {
+            let mut i = 0;
+            while i < U8G2_FONT_DATA_STRUCT_SIZE {
+                index.font_header[i] = data[i];
+                i += 1;
+            }
+        }
+
+        let Some(glyphs) = scan_unicode_glyphs(data) else {
+            return index;
+        };
+
+        let samples = if glyphs.count < N { glyphs.count } else { N };
+
+        let mut pos = glyphs.glyphs_start;
+        let mut glyph = 0;
+        while index.len < samples {
+            if glyph == index.len * glyphs.count / samples {
+                index.entries[index.len] = GlyphIndexEntry {
+                    encoding: u16::from_be_bytes([data[pos], data[pos + 1]]),
+                    offset: (pos - glyphs.section_start) as u32,
+                };
+                index.len += 1;
+            }
+            pos += data[pos + 2] as usize;
+            glyph += 1;
+        }
+
+        index.usable = true;
+        index
+    }
+
+    /// Checks whether this index was created from the given font data.
+    ///
+    /// Compares the size and the header of the font, which includes the
+    /// offset of the unicode section, and the encodings of all sampled glyphs.
+    pub(crate) const fn matches(&self, data: &[u8]) -> bool {
+        if data.len() != self.font_size {
+            return false;
+        }
+
+        if data.len() >= U8G2_FONT_DATA_STRUCT_SIZE {
+            let mut i = 0;
+            while i < U8G2_FONT_DATA_STRUCT_SIZE {
+                if data[i] != self.font_header[i] {
+                    return false;
+                }
+                i += 1;
+            }
+        }
+
+        if !self.usable {
+            return true;
+        }
+
+        let Some(section_offset) = read_u16(data, 21) else {
+            return false;
+        };
+        let section_start = U8G2_FONT_DATA_STRUCT_SIZE + section_offset as usize;
+
+        let mut i = 0;
+        while i < self.len {
+            let entry = &self.entries[i];
+            match read_u16(data, section_start + entry.offset as usize) {
+                Some(encoding) if encoding == entry.encoding => {}
+                _ => return false,
+            }
+            i += 1;
+        }
+
+        true
+    }
+
+    /// The lookup part of the index, or `None` if the font couldn't be indexed.
+    pub(crate) const fn get(&self) -> Option<GlyphIndexRef<'_>> {
+        if self.usable {
+            Some(GlyphIndexRef {
+                entries: DebugIgnore(self.entries.as_slice().split_at(self.len).0),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl GlyphIndexRef<'_> {
+    /// Computes where the search for the given glyph should start,
+    /// relative to the start of the unicode section.
+    ///
+    /// Returns `None` if the font doesn't contain any unicode glyphs.
+    pub fn calculate_jump_offset(&self, encoding: u16) -> Option<usize> {
+        let following = self
+            .entries
+            .partition_point(|entry| entry.encoding <= encoding);
+        let entry = self.entries.get(following.saturating_sub(1))?;
+        Some(entry.offset as usize)
+    }
+}
+
+const fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    if pos + 1 < data.len() {
+        Some(u16::from_be_bytes([data[pos], data[pos + 1]]))
+    } else {
+        None
+    }
+}
+
+/// Walks the unicode glyph list and checks that it is strictly sorted and consistent with
+/// the unicode jump table. Only then, searching from an index sample is equivalent to
+/// searching from the jump table.
+const fn scan_unicode_glyphs(data: &[u8]) -> Option<UnicodeGlyphs> {
+    let Some(section_offset) = read_u16(data, 21) else {
+        return None;
+    };
+    let section_start = U8G2_FONT_DATA_STRUCT_SIZE + section_offset as usize;
+
+    let mut table_pos = section_start;
+    let (Some(jump_distance), Some(mut upper_limit)) =
+        (read_u16(data, table_pos), read_u16(data, table_pos + 2))
+    else {
+        return None;
+    };
+    table_pos += 4;
+
+    let glyphs_start = section_start + jump_distance as usize;
+    let mut block_start = glyphs_start;
+    let mut pos = glyphs_start;
+    let mut previous_encoding = 0;
+    let mut count = 0;
+
+    loop {
+        // Enter all jump table blocks that start at the current glyph
+        while upper_limit != 0xffff {
+            let (Some(jump_distance), Some(next_upper_limit)) =
+                (read_u16(data, table_pos), read_u16(data, table_pos + 2))
+            else {
+                return None;
+            };
+            let next_block_start = block_start + jump_distance as usize;
+            if next_block_start > pos {
+                break;
+            }
+            if next_block_start < pos {
+                return None;
+            }
+            block_start = next_block_start;
+            upper_limit = next_upper_limit;
+            table_pos += 4;
+        }
+
+        let Some(encoding) = read_u16(data, pos) else {
+            return None;
+        };
+        if encoding == 0 {
+            break;
+        }
+        if encoding <= previous_encoding || encoding > upper_limit || pos + 2 >= data.len() {
+            return None;
+        }
+        let offset = data[pos + 2] as usize;
+        if offset == 0 {
+            return None;
+        }
+
+        previous_encoding = encoding;
+        count += 1;
+        pos += offset;
+    }
+
+    if upper_limit != 0xffff || glyphs_start < table_pos {
+        return None;
+    }
+
+    Some(UnicodeGlyphs {
+        section_start,
+        glyphs_start,
+        count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+
+    const HEADER: [u8; 23] = [
+        0, 0, 4, 4, 8, 8, 8, 8, 8, 1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 2,
+    ];
+
+    fn font(unicode: &[u8]) -> Vec<u8> {
+        let mut data = Vec::from(HEADER);
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(unicode);
+        data
+    }
+
+    fn encodings<const N: usize>(index: &GlyphIndex<N>) -> Vec<u16> {
+        index
+            .get()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| entry.encoding)
+            .collect()
+    }
+
+    const THREE_GLYPHS: &[u8] = &[
+        0, 8, 0x01, 0x01, // Jump table entry
+        0, 6, 255, 255, // Jump table end
+        0x01, 0x00, 3, // First glyph
+        0x01, 0x01, 3, // Second glyph
+        0x01, 0x05, 3, // Third glyph
+        0, 0, // End marker
+    ];
+
+    #[test]
+    fn indexes_all_glyphs_if_large_enough() {
+        let data = font(THREE_GLYPHS);
+        let index = GlyphIndex::<8>::from_data(&data);
+        assert_eq!(encodings(&index), [0x100, 0x101, 0x105]);
+
+        let index = index.get().unwrap();
+        assert_eq!(index.calculate_jump_offset(0xff), Some(8));
+        assert_eq!(index.calculate_jump_offset(0x100), Some(8));
+        assert_eq!(index.calculate_jump_offset(0x104), Some(11));
+        assert_eq!(index.calculate_jump_offset(0x105), Some(14));
+        assert_eq!(index.calculate_jump_offset(0xffff), Some(14));
+    }
+
+    #[test]
+    fn samples_glyphs_if_too_small() {
+        let data = font(THREE_GLYPHS);
+        assert_eq!(
+            encodings(&GlyphIndex::<2>::from_data(&data)),
+            [0x100, 0x101]
+        );
+        assert_eq!(encodings(&GlyphIndex::<1>::from_data(&data)), [0x100]);
+    }
+
+    #[test]
+    fn indexes_font_without_unicode_glyphs() {
+        let data = font(&[0, 4, 255, 255, 0, 0]);
+        let index = GlyphIndex::<4>::from_data(&data);
+        assert_eq!(index.get().unwrap().calculate_jump_offset(0x100), None);
+    }
+
+    #[test]
+    fn rejects_unsorted_glyphs() {
+        let mut data = font(THREE_GLYPHS);
+        data[25 + 15] = 0x00;
+        assert!(GlyphIndex::<8>::from_data(&data).get().is_none());
+    }
+
+    #[test]
+    fn rejects_glyphs_beyond_jump_table_limit() {
+        let mut data = font(THREE_GLYPHS);
+        data[25 + 3] = 0x00;
+        assert!(GlyphIndex::<8>::from_data(&data).get().is_none());
+    }
+
+    #[test]
+    fn indexes_all_builtin_fonts() {
+        let fonts_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/fonts");
+
+        for entry in std::fs::read_dir(fonts_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "u8g2font") {
+                let data = std::fs::read(&path).unwrap();
+                assert!(
+                    GlyphIndex::<16>::from_data(&data).get().is_some(),
+                    "{} can't be indexed",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matches_only_the_indexed_font() {
+        let data = font(THREE_GLYPHS);
+        let index = GlyphIndex::<8>::from_data(&data);
+        assert!(index.matches(&data));
+
+        let mut other_header = data.clone();
+        other_header[3] = 5;
+        assert!(!index.matches(&other_header));
+
+        let mut other_glyphs = data.clone();
+        other_glyphs[25 + 12] = 0x02;
+        assert!(!index.matches(&other_glyphs));
+
+        assert!(!index.matches(&data[..data.len() - 1]));
+    }
+
+    #[test]
+    fn rejects_corrupt_data() {
+        let data = font(THREE_GLYPHS);
+        for len in 0..data.len() {
+            assert!(GlyphIndex::<8>::from_data(&data[..len]).get().is_none());
+        }
+    }
+}
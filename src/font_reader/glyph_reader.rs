@@ -2,12 +2,13 @@ use embedded_graphics_core::prelude::{Point, Size};
 
 use crate::{
     font_reader::{glyph_renderer::GlyphRenderer, FontReader},
+    types::GlyphMetrics,
     utils::DebugIgnore,
 };
 
 #[derive(Clone, Debug)]
-pub struct GlyphReader {
-    data: DebugIgnore<&'static [u8]>,
+pub struct GlyphReader<'a> {
+    data: DebugIgnore<&'a [u8]>,
     bit_pos: u8,
     current_byte: u8,
     glyph_width: u8,
@@ -17,10 +18,18 @@ pub struct GlyphReader {
     advance: i8,
     bitcount_0: u8,
     bitcount_1: u8,
+    exhausted: bool,
+    tofu: bool,
+    bold: bool,
+    italic: bool,
+    cell_width: Option<i32>,
 }
 
-impl GlyphReader {
-    pub fn new(data: &'static [u8], font: &FontReader) -> Self {
+/// The number of rows after which synthetic italic glyphs shift by one more pixel.
+const ITALIC_SLOPE: i32 = 4;
+
+impl<'a> GlyphReader<'a> {
+    pub fn new(data: &'a [u8], font: &FontReader) -> Self {
         let mut this = Self {
             data: DebugIgnore(data),
             // Start at 8 to mark current_byte as invalid
@@ -33,6 +42,11 @@ impl GlyphReader {
             advance: 0,
             bitcount_0: font.m0,
             bitcount_1: font.m1,
+            exhausted: false,
+            tofu: false,
+            bold: false,
+            italic: false,
+            cell_width: None,
         };
 
         this.glyph_width = this.read_unsigned(font.bitcnt_w);
@@ -45,6 +59,106 @@ impl GlyphReader {
         this
     }
 
+    /// Creates a placeholder glyph for characters that are missing in the font.
+    ///
+    /// It has the size of the font bounding box and gets rendered as a hollow box.
+    pub fn new_tofu(font: &FontReader) -> Self {
+        let width = font.font_bounding_box_width.max(0) as u8;
+        Self {
+            data: DebugIgnore(&[]),
+            bit_pos: 8,
+            current_byte: 0,
+            glyph_width: width,
+            glyph_height: font.font_bounding_box_height.max(0) as u8,
+            offset_x: 0,
+            offset_y: font.font_bounding_box_y_offset,
+            advance: i8::try_from(width.saturating_add(1)).unwrap_or(i8::MAX),
+            bitcount_0: font.m0,
+            bitcount_1: font.m1,
+            exhausted: false,
+            tofu: true,
+            bold: false,
+            italic: false,
+            cell_width: None,
+        }
+    }
+
+    pub fn is_tofu(&self) -> bool {
+        self.tofu
+    }
+
+    /// Applies synthetic styles to the glyph.
+    ///
+    /// Bold smears every pixel one pixel to the right, italic shears the glyph
+    /// so that rows further above the baseline move further to the right.
+    pub fn with_synthetic_style(mut self, bold: bool, italic: bool) -> Self {
+        self.bold = bold;
+        self.italic = italic;
+        self
+    }
+
+    /// Centers the glyph in a cell of the given width, which replaces its advance.
+    ///
+    /// Has to be applied after the synthetic styles, as bold glyphs are wider.
+    pub fn with_cell_width(mut self, width: i32) -> Self {
+        self.cell_width = Some(width);
+        self
+    }
+
+    /// The horizontal shift that centers the glyph in its cell.
+    fn cell_shift(&self) -> i32 {
+        match self.cell_width {
+            Some(width) => (width - self.unaligned_advance()).div_euclid(2),
+            None => 0,
+        }
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    /// Whether the glyph pixels have to be modified while rendering.
+    pub fn is_styled(&self) -> bool {
+        self.bold || self.italic
+    }
+
+    /// The horizontal shift of the given row of the unstyled glyph, relative to its bottom row.
+    pub fn italic_shift(&self, row: i32) -> i32 {
+        if !self.italic {
+            return 0;
+        }
+
+        let row_above_baseline =
+            |row: i32| self.offset_y as i32 + self.glyph_height as i32 - 1 - row;
+        let bottom_row = self.glyph_height as i32 - 1;
+
+        row_above_baseline(row).div_euclid(ITALIC_SLOPE)
+            - row_above_baseline(bottom_row).div_euclid(ITALIC_SLOPE)
+    }
+
+    /// The horizontal shift of the bottom row, relative to the glyph position.
+    fn bottom_italic_shift(&self) -> i32 {
+        if self.italic {
+            (self.offset_y as i32).div_euclid(ITALIC_SLOPE)
+        } else {
+            0
+        }
+    }
+
+    /// How far the slanted glyph reaches further to the right than the upright one.
+    ///
+    /// This is the shift of its top row, as long as it is above the baseline.
+    fn italic_overhang(&self) -> i32 {
+        if self.glyph_width == 0 || self.glyph_height == 0 {
+            return 0;
+        }
+        (self.bottom_italic_shift() + self.italic_shift(0)).max(0)
+    }
+
     pub fn read_unsigned(&mut self, bits: u8) -> u8 {
         let bit_start = self.bit_pos;
         let mut bit_end = bit_start + bits;
@@ -52,10 +166,20 @@ impl GlyphReader {
         // Read from current byte
         let mut value = self.current_byte.overflowing_shr(bit_start as u32).0;
 
-        // If necessary, fetch next byte
+        // If necessary, fetch next byte.
+        // Missing data reads as zeros; it only counts as exhausted if bits were actually
+        // requested from beyond the end, as the last glyph might end exactly at the end.
         if bit_end >= 8 {
-            let value2 = *self.data.first().unwrap();
-            *self.data = self.data.get(1..).unwrap();
+            let value2 = match self.data.split_first() {
+                Some((&first, rest)) => {
+                    *self.data = rest;
+                    first
+                }
+                None => {
+                    self.exhausted |= bit_end > 8;
+                    0
+                }
+            };
             bit_end -= 8;
             self.current_byte = value2;
 
@@ -67,31 +191,71 @@ impl GlyphReader {
         value & (((1u16 << bits) - 1) as u8)
     }
 
+    /// Whether the glyph tried to read beyond the end of the font data,
+    /// which can only happen with corrupt fonts.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
     pub fn read_signed(&mut self, bits: u8) -> i8 {
         (self.read_unsigned(bits) as i8).wrapping_sub(1 << (bits - 1))
     }
 
     pub fn topleft(&self, pos: &Point) -> Point {
         Point::new(
-            pos.x + self.offset_x as i32,
+            self.left(pos.x),
             pos.y - (self.glyph_height as i32 + self.offset_y as i32),
         )
     }
 
     pub fn left(&self, pos_x: i32) -> i32 {
-        pos_x + self.offset_x as i32
+        pos_x + self.offset_x as i32 + self.bottom_italic_shift() + self.cell_shift()
     }
 
+    /// The size of the rendered glyph, including synthetic styles.
     pub fn size(&self) -> Size {
+        Size::new(self.width(), self.glyph_height as u32)
+    }
+
+    /// The width of the rendered glyph, including synthetic styles.
+    pub fn width(&self) -> u32 {
+        let width = self.glyph_width as u32;
+        if width == 0 || self.glyph_height == 0 {
+            return width;
+        }
+
+        // The top row is shifted the furthest
+        let shear = self.italic_shift(0) as u32;
+        width + shear + u32::from(self.bold)
+    }
+
+    /// The size of the glyph as stored in the font.
+    pub fn unstyled_size(&self) -> Size {
         Size::new(self.glyph_width as u32, self.glyph_height as u32)
     }
 
-    pub fn width(&self) -> u8 {
-        self.glyph_width
+    /// The unscaled metrics of the rendered glyph, including synthetic styles.
+    pub fn metrics(&self) -> GlyphMetrics {
+        let size = self.size();
+        GlyphMetrics {
+            width: size.width,
+            height: size.height,
+            offset_x: self.left(0),
+            offset_y: self.offset_y as i32,
+            advance: self.advance(),
+        }
+    }
+
+    pub fn advance(&self) -> i32 {
+        self.cell_width.unwrap_or_else(|| self.unaligned_advance())
     }
 
-    pub fn advance(&self) -> i8 {
-        self.advance
+    /// The advance of the glyph, if it isn't in a cell.
+    ///
+    /// Synthetic styles widen the glyph to the right, so they widen the advance
+    /// by the same amount to keep the distance to the next glyph.
+    fn unaligned_advance(&self) -> i32 {
+        i32::from(self.advance) + i32::from(self.bold) + self.italic_overhang()
     }
 
     pub fn read_runlength_0(&mut self) -> u8 {
@@ -102,7 +266,7 @@ impl GlyphReader {
         self.read_unsigned(self.bitcount_1)
     }
 
-    pub fn create_renderer(&self) -> GlyphRenderer {
+    pub fn create_renderer(&self) -> GlyphRenderer<'a> {
         GlyphRenderer::new(self)
     }
 }
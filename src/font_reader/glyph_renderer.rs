@@ -1,17 +1,21 @@
 use embedded_graphics_core::{
-    prelude::{DrawTarget, Point},
+    prelude::{DrawTarget, Point, Size},
     primitives::Rectangle,
     Pixel,
 };
 
-use crate::{font_reader::glyph_reader::GlyphReader, Error};
+use crate::{
+    error::DrawError,
+    font_reader::glyph_reader::GlyphReader,
+    utils::{fill_solid_clipped, is_fully_visible, Transform},
+};
 
-pub struct GlyphRenderer {
-    glyph: GlyphReader,
+pub struct GlyphRenderer<'a> {
+    glyph: GlyphReader<'a>,
 }
 
-impl GlyphRenderer {
-    pub fn new(glyph: &GlyphReader) -> Self {
+impl<'a> GlyphRenderer<'a> {
+    pub fn new(glyph: &GlyphReader<'a>) -> Self {
         Self {
             glyph: glyph.clone(),
         }
@@ -24,22 +28,41 @@ impl GlyphRenderer {
     pub fn render_as_box_fill<Display>(
         mut self,
         position: Point,
+        transform: &Transform,
         display: &mut Display,
         foreground_color: Display::Color,
         background_color: Display::Color,
-    ) -> Result<Rectangle, Error<Display::Error>>
+    ) -> Result<Rectangle, DrawError<Display::Error>>
     where
         Display: DrawTarget,
     {
         let glyph_bounding_box = self.get_glyph_bounding_box(position);
 
+        // `fill_contiguous` requires the pixels in the order of the transformed glyph,
+        // which can't be decoded in a streaming fashion.
+        // Paint the background first and then draw the glyph on top instead.
+        // Tofu glyphs have no pixel data to stream and styled glyphs don't match their
+        // pixel data any more, so they take the same route. So do partially visible glyphs,
+        // as only the visible part of them gets drawn that way.
+        if !transform.is_identity()
+            || self.glyph.is_tofu()
+            || self.glyph.is_styled()
+            || !is_fully_visible(&glyph_bounding_box, &display.bounding_box())
+        {
+            self.fill_background(glyph_bounding_box, transform, display, background_color)?;
+            return self.render_transparent(position, transform, display, foreground_color);
+        }
+
         let color_iter = {
             let mut num_zeros = self.glyph.read_runlength_0();
             let mut num_ones = self.glyph.read_runlength_1();
             let mut num_zeros_leftover = num_zeros;
             let mut num_ones_leftover = num_ones;
             move || -> Option<Display::Color> {
-                if num_zeros_leftover == 0 && num_ones_leftover == 0 {
+                while num_zeros_leftover == 0 && num_ones_leftover == 0 {
+                    if self.glyph.is_exhausted() {
+                        return Some(background_color);
+                    }
                     let repeat = self.glyph.read_unsigned(1) != 0;
                     if !repeat {
                         num_zeros = self.glyph.read_runlength_0();
@@ -63,23 +86,129 @@ impl GlyphRenderer {
 
         display
             .fill_contiguous(&glyph_bounding_box, core::iter::from_fn(color_iter))
-            .map_err(Error::DisplayError)?;
+            .map_err(DrawError::DisplayError)?;
 
         Ok(glyph_bounding_box)
     }
 
+    /// Fills the area behind the glyph.
+    ///
+    /// Italic glyphs lean into the area of their neighbours, so their background gets
+    /// sheared as well. This way, the backgrounds of adjacent glyphs don't overlap.
+    fn fill_background<Display>(
+        &self,
+        glyph_bounding_box: Rectangle,
+        transform: &Transform,
+        display: &mut Display,
+        background_color: Display::Color,
+    ) -> Result<(), DrawError<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        if !self.glyph.is_italic() {
+            return fill_solid_clipped(
+                display,
+                &transform.rect(glyph_bounding_box),
+                background_color,
+            )
+            .map_err(DrawError::DisplayError);
+        }
+
+        let Size { width, height } = self.glyph.unstyled_size();
+        let row_size = Size::new(width + u32::from(self.glyph.is_bold()), 1);
+        for row in 0..height as i32 {
+            let offset = Point::new(self.glyph.italic_shift(row), row);
+            let row_rect = Rectangle::new(glyph_bounding_box.top_left + offset, row_size);
+            fill_solid_clipped(display, &transform.rect(row_rect), background_color)
+                .map_err(DrawError::DisplayError)?;
+        }
+
+        Ok(())
+    }
+
     pub fn render_transparent<Display>(
-        mut self,
+        self,
         position: Point,
+        transform: &Transform,
         display: &mut Display,
         foreground_color: Display::Color,
-    ) -> Result<Rectangle, Error<Display::Error>>
+    ) -> Result<Rectangle, DrawError<Display::Error>>
     where
         Display: DrawTarget,
     {
         let glyph_bounding_box = self.get_glyph_bounding_box(position);
-        let width = glyph_bounding_box.size.width as i32;
-        let height = glyph_bounding_box.size.height as i32;
+        let runs = self.foreground_runs();
+        draw_runs(
+            runs,
+            glyph_bounding_box.top_left,
+            transform,
+            display,
+            foreground_color,
+        )?;
+
+        Ok(glyph_bounding_box)
+    }
+
+    /// Renders copies of the glyph, shifted by each of the `offsets`.
+    ///
+    /// Used for outlines and shadows. Returns the bounding box of the unshifted glyph.
+    pub fn render_shifted<Display>(
+        self,
+        position: Point,
+        transform: &Transform,
+        display: &mut Display,
+        color: Display::Color,
+        offsets: &[Point],
+    ) -> Result<Rectangle, DrawError<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        let glyph_bounding_box = self.get_glyph_bounding_box(position);
+        let runs = self.foreground_runs().flat_map(|run| {
+            offsets
+                .iter()
+                .map(move |&offset| Rectangle::new(run.top_left + offset, run.size))
+        });
+        draw_runs(runs, glyph_bounding_box.top_left, transform, display, color)?;
+
+        Ok(glyph_bounding_box)
+    }
+
+    /// Returns the horizontal runs of foreground pixels as rectangles of height one,
+    /// relative to the top left corner of the glyph.
+    fn foreground_runs(self) -> impl Iterator<Item = Rectangle> + 'a {
+        merge_runs(self.foreground_pixels())
+    }
+
+    /// Returns the positions of all foreground pixels,
+    /// relative to the top left corner of the glyph.
+    fn foreground_pixels(self) -> impl Iterator<Item = Point> + 'a {
+        let glyph = self.glyph.clone();
+
+        let (tofu, decoded) = if glyph.is_tofu() {
+            (Some(tofu_pixels(glyph.unstyled_size())), None)
+        } else {
+            (None, Some(self.decode_foreground_pixels()))
+        };
+
+        let pixels = tofu
+            .into_iter()
+            .flatten()
+            .chain(decoded.into_iter().flatten());
+
+        // Italic shears every row, bold draws every pixel a second time one pixel to the right
+        pixels.flat_map(move |p| {
+            let p = Point::new(p.x + glyph.italic_shift(p.y), p.y);
+            let bold = glyph.is_bold().then_some(p + Point::new(1, 0));
+            core::iter::once(p).chain(bold)
+        })
+    }
+
+    /// Decodes the run-length encoded glyph data into foreground pixel positions.
+    fn decode_foreground_pixels(mut self) -> impl Iterator<Item = Point> + 'a {
+        let width = self.glyph.unstyled_size().width as i32;
+        let height = self.glyph.unstyled_size().height as i32;
+        let is_empty = width == 0 || height == 0;
 
         let pixel_iter = {
             let mut num_zeros = self.glyph.read_runlength_0();
@@ -87,19 +216,22 @@ impl GlyphRenderer {
             let mut num_ones_leftover = num_ones;
 
             let mut x = num_zeros as i32;
-            let mut y = 0i32;
+            let mut y = if is_empty { height } else { 0 };
 
-            while x >= width {
+            while !is_empty && x >= width {
                 x -= width;
                 y += 1;
             }
 
-            move || -> Option<Pixel<Display::Color>> {
+            move || -> Option<Point> {
                 if y >= height {
                     return None;
                 }
 
                 while num_ones_leftover == 0 {
+                    if self.glyph.is_exhausted() {
+                        return None;
+                    }
                     let repeat = self.glyph.read_unsigned(1) != 0;
                     if !repeat {
                         num_zeros = self.glyph.read_runlength_0();
@@ -116,10 +248,7 @@ impl GlyphRenderer {
                     num_ones_leftover = num_ones;
                 }
 
-                let pixel = Pixel(
-                    glyph_bounding_box.top_left + Point::new(x, y),
-                    foreground_color,
-                );
+                let pixel = Point::new(x, y);
                 x += 1;
                 if x >= width {
                     x -= width;
@@ -131,10 +260,158 @@ impl GlyphRenderer {
             }
         };
 
+        core::iter::from_fn(pixel_iter)
+    }
+}
+
+/// Merges pixels that continue the current row into horizontal runs.
+///
+/// The pixels are expected in row-major order, as decoded from the glyph data.
+/// Pixels that overlap the current run, like the doubled pixels of bold glyphs,
+/// get merged as well.
+fn merge_runs(pixels: impl Iterator<Item = Point>) -> impl Iterator<Item = Rectangle> {
+    let mut pixels = pixels.peekable();
+    core::iter::from_fn(move || {
+        let start = pixels.next()?;
+        let mut end = start.x + 1;
+        while let Some(p) = pixels.next_if(|p| p.y == start.y && (start.x..=end).contains(&p.x)) {
+            end = end.max(p.x + 1);
+        }
+        Some(Rectangle::new(start, Size::new((end - start.x) as u32, 1)))
+    })
+}
+
+/// Draws the visible parts of the runs relative to the text space position `top_left`.
+///
+/// Scaled runs get drawn as one filled rectangle each, instead of one per pixel.
+fn draw_runs<Display>(
+    runs: impl Iterator<Item = Rectangle>,
+    top_left: Point,
+    transform: &Transform,
+    display: &mut Display,
+    color: Display::Color,
+) -> Result<(), DrawError<Display::Error>>
+where
+    Display: DrawTarget,
+{
+    if transform.scale() == 1 {
+        let clip = display.bounding_box();
         display
-            .draw_iter(core::iter::from_fn(pixel_iter))
-            .map_err(Error::DisplayError)?;
+            .draw_iter(
+                runs.flat_map(|run| {
+                    (0..run.size.width as i32).map(move |x| run.top_left + Point::new(x, 0))
+                })
+                .map(|p| transform.pixel(top_left + p))
+                .filter(|&p| clip.contains(p))
+                .map(|p| Pixel(p, color)),
+            )
+            .map_err(DrawError::DisplayError)
+    } else {
+        for run in runs {
+            fill_solid_clipped(
+                display,
+                &transform.rect(Rectangle::new(top_left + run.top_left, run.size)),
+                color,
+            )
+            .map_err(DrawError::DisplayError)?;
+        }
+        Ok(())
+    }
+}
 
-        Ok(glyph_bounding_box)
+/// The outline of a box of the given size, used to draw missing glyphs.
+fn tofu_pixels(size: Size) -> impl Iterator<Item = Point> {
+    let width = size.width as i32;
+    let height = size.height as i32;
+    (0..height).flat_map(move |y| {
+        (0..width)
+            .filter(move |&x| x == 0 || y == 0 || x == width - 1 || y == height - 1)
+            .map(move |x| Point::new(x, y))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use core::convert::Infallible;
+    use std::vec::Vec;
+
+    use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::Dimensions};
+
+    use super::*;
+    use crate::types::FontRotation;
+
+    /// Records the areas passed to `fill_solid`.
+    struct FillRecorder {
+        fills: Vec<Rectangle>,
+    }
+
+    impl Dimensions for FillRecorder {
+        fn bounding_box(&self) -> Rectangle {
+            Rectangle::new(Point::zero(), Size::new(100, 100))
+        }
+    }
+
+    impl DrawTarget for FillRecorder {
+        type Color = BinaryColor;
+        type Error = Infallible;
+
+        fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            unreachable!("scaled runs should be drawn as filled rectangles")
+        }
+
+        fn fill_solid(&mut self, area: &Rectangle, _color: Self::Color) -> Result<(), Self::Error> {
+            self.fills.push(*area);
+            Ok(())
+        }
+    }
+
+    fn run(x: i32, y: i32, width: u32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(width, 1))
+    }
+
+    #[test]
+    fn merge_runs_joins_adjacent_pixels_of_a_row() {
+        let pixels =
+            [(0, 0), (1, 0), (2, 0), (4, 0), (0, 1), (1, 1)].map(|(x, y)| Point::new(x, y));
+
+        let runs = merge_runs(pixels.into_iter()).collect::<Vec<_>>();
+
+        assert_eq!(runs, [run(0, 0, 3), run(4, 0, 1), run(0, 1, 2)]);
+    }
+
+    #[test]
+    fn merge_runs_joins_overlapping_bold_pixels() {
+        let pixels = [(2, 3), (3, 3), (3, 3), (4, 3)].map(|(x, y)| Point::new(x, y));
+
+        let runs = merge_runs(pixels.into_iter()).collect::<Vec<_>>();
+
+        assert_eq!(runs, [run(2, 3, 3)]);
+    }
+
+    #[test]
+    fn scaled_runs_are_filled_as_one_rectangle() {
+        let mut display = FillRecorder { fills: Vec::new() };
+        let transform = Transform::new(Point::zero(), FontRotation::Rotate0, 3);
+
+        draw_runs(
+            [run(0, 0, 4), run(1, 1, 2)].into_iter(),
+            Point::new(1, 2),
+            &transform,
+            &mut display,
+            BinaryColor::On,
+        )
+        .unwrap();
+
+        assert_eq!(
+            display.fills,
+            [
+                Rectangle::new(Point::new(3, 6), Size::new(12, 3)),
+                Rectangle::new(Point::new(6, 9), Size::new(6, 3)),
+            ]
+        );
     }
 }
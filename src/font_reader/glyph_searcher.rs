@@ -1,19 +1,27 @@
 use crate::font_reader::{
     glyph_reader::GlyphReader, unicode_jumptable_reader::UnicodeJumptableReader, FontReader,
+    U8G2_FONT_DATA_STRUCT_SIZE,
 };
 
-pub struct GlyphSearcher<'a, const CHAR_WIDTH: usize> {
-    data: &'static [u8],
-    font: &'a FontReader,
+// Out-of-bounds accesses are treated like the end of the glyph table
+// instead of panicking, so that corrupt font data can't crash the program.
+pub struct GlyphSearcher<'a, 'f, const CHAR_WIDTH: usize> {
+    data: &'a [u8],
+    font: &'f FontReader<'a>,
 }
 
-impl<const CHAR_WIDTH: usize> GlyphSearcher<'_, CHAR_WIDTH> {
+impl<'a, const CHAR_WIDTH: usize> GlyphSearcher<'a, '_, CHAR_WIDTH> {
     pub fn jump_by(&mut self, offset: usize) {
-        self.data = &self.data[offset..];
+        self.data = self.data.get(offset..).unwrap_or_default();
     }
 
     fn get_offset(&self) -> u8 {
-        self.data.get(CHAR_WIDTH).cloned().unwrap()
+        self.data.get(CHAR_WIDTH).cloned().unwrap_or(0)
+    }
+
+    /// Whether the searcher reached the entry that terminates the glyph list.
+    pub fn is_at_end(&self) -> bool {
+        self.get_offset() == 0
     }
 
     pub fn jump_to_next(&mut self) -> bool {
@@ -26,29 +34,33 @@ impl<const CHAR_WIDTH: usize> GlyphSearcher<'_, CHAR_WIDTH> {
         }
     }
 
-    pub fn into_glyph_reader(self) -> GlyphReader {
-        GlyphReader::new(self.data.get((CHAR_WIDTH + 1)..).unwrap(), self.font)
+    pub fn into_glyph_reader(self) -> GlyphReader<'a> {
+        GlyphReader::new(
+            self.data.get((CHAR_WIDTH + 1)..).unwrap_or_default(),
+            self.font,
+        )
     }
 }
 
-const U8G2_FONT_DATA_STRUCT_SIZE: usize = 23;
-
-impl<'a> GlyphSearcher<'a, 1> {
-    pub fn new(font: &'a FontReader) -> Self {
+impl<'a, 'f> GlyphSearcher<'a, 'f, 1> {
+    pub fn new(font: &'f FontReader<'a>) -> Self {
         Self {
-            data: &font.data[U8G2_FONT_DATA_STRUCT_SIZE..],
+            data: font
+                .data
+                .get(U8G2_FONT_DATA_STRUCT_SIZE..)
+                .unwrap_or_default(),
             font,
         }
     }
 
-    pub fn get_ch(&self) -> u8 {
-        self.data.first().cloned().unwrap()
+    pub fn get_ch(&self) -> Option<u8> {
+        self.data.first().cloned()
     }
 
     pub fn into_unicode_mode(
         mut self,
         offset: u16,
-    ) -> (GlyphSearcher<'a, 2>, UnicodeJumptableReader) {
+    ) -> (GlyphSearcher<'a, 'f, 2>, UnicodeJumptableReader<'a>) {
         self.jump_by(offset.into());
 
         (
@@ -61,11 +73,11 @@ impl<'a> GlyphSearcher<'a, 1> {
     }
 }
 
-impl GlyphSearcher<'_, 2> {
-    pub fn get_ch(&self) -> u16 {
-        u16::from_be_bytes([
-            self.data.first().cloned().unwrap(),
-            self.data.get(1).cloned().unwrap(),
-        ])
+impl GlyphSearcher<'_, '_, 2> {
+    pub fn get_ch(&self) -> Option<u16> {
+        Some(u16::from_be_bytes([
+            self.data.first().cloned()?,
+            self.data.get(1).cloned()?,
+        ]))
     }
 }
@@ -1,15 +1,29 @@
-use crate::{utils::DebugIgnore, Font, LookupError};
+use embedded_graphics_core::prelude::Point;
 
-use self::{glyph_reader::GlyphReader, glyph_searcher::GlyphSearcher};
+use crate::{
+    types::{CellWidth, FixedWidth, FontInfo, FontRotation, KerningPair, TextDecorations},
+    utils::{DebugIgnore, Transform},
+    Font, FontDataError, LookupError,
+};
 
+use self::{glyph_index::GlyphIndexRef, glyph_searcher::GlyphSearcher};
+
+pub(crate) use self::glyph_reader::GlyphReader;
+pub use self::{encodings::Encodings, glyph_index::GlyphIndex};
+
+mod encodings;
+mod glyph_index;
 mod glyph_reader;
 mod glyph_renderer;
 mod glyph_searcher;
 mod unicode_jumptable_reader;
+mod validator;
+
+const U8G2_FONT_DATA_STRUCT_SIZE: usize = 23;
 
 #[derive(Debug, Clone)]
-pub struct FontReader {
-    pub data: DebugIgnore<&'static [u8]>,
+pub struct FontReader<'a> {
+    pub data: DebugIgnore<&'a [u8]>,
     pub supports_background_color: bool,
     pub glyph_count: u8,
     pub m0: u8,
@@ -31,12 +45,34 @@ pub struct FontReader {
     pub array_offset_lower_a: u16,
     pub array_offset_0x0100: u16,
     pub ignore_unknown_glyphs: bool,
+    pub replacement_char: Option<char>,
     pub line_height: u32,
+    pub rotation: FontRotation,
+    pub scale: u32,
+    pub fallback: Option<&'a FontReader<'a>>,
+    pub glyph_index: Option<GlyphIndexRef<'a>>,
+    pub kerning: DebugIgnore<&'a [KerningPair]>,
+    pub letter_spacing: i32,
+    pub word_spacing: i32,
+    pub synthetic_bold: bool,
+    pub synthetic_italic: bool,
+    pub fixed_width: FixedWidth,
+    pub decorations: TextDecorations,
+    pub emulate_background: bool,
 }
 
-impl FontReader {
+impl FontReader<'static> {
     pub const fn new<F: Font>() -> Self {
-        let data = F::DATA;
+        Self::from_data(F::DATA)
+    }
+}
+
+impl<'a> FontReader<'a> {
+    pub const fn from_data(data: &'a [u8]) -> Self {
+        assert!(
+            data.len() >= U8G2_FONT_DATA_STRUCT_SIZE,
+            "The font data is too short to contain a font header"
+        );
 
         let mut this = Self {
             data: DebugIgnore(data),
@@ -61,36 +97,307 @@ impl FontReader {
             array_offset_lower_a: u16::from_be_bytes([data[19], data[20]]),
             array_offset_0x0100: u16::from_be_bytes([data[21], data[22]]),
             ignore_unknown_glyphs: false,
+            replacement_char: None,
             line_height: 0,
+            rotation: FontRotation::Rotate0,
+            scale: 1,
+            fallback: None,
+            glyph_index: None,
+            kerning: DebugIgnore(&[]),
+            letter_spacing: 0,
+            word_spacing: 0,
+            synthetic_bold: false,
+            synthetic_italic: false,
+            fixed_width: FixedWidth::Proportional,
+            decorations: TextDecorations::NONE,
+            emulate_background: false,
         };
         this.line_height = this.get_default_line_height() as u32;
         this
     }
 
+    pub fn try_from_data(data: &'a [u8]) -> Result<Self, FontDataError> {
+        validator::validate(data)?;
+        Ok(Self::from_data(data))
+    }
+
     pub const fn with_ignore_unknown_glyphs(mut self, ignore: bool) -> Self {
         self.ignore_unknown_glyphs = ignore;
         self
     }
 
+    pub const fn with_replacement_char(mut self, replacement: Option<char>) -> Self {
+        self.replacement_char = replacement;
+        self
+    }
+
     pub const fn with_line_height(mut self, line_height: u32) -> Self {
         self.line_height = line_height;
         self
     }
 
+    pub const fn with_rotation(mut self, rotation: FontRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub const fn with_scale(mut self, scale: u32) -> Self {
+        assert!(scale > 0);
+        self.scale = scale;
+        self
+    }
+
+    pub const fn with_fallback(mut self, fallback: &'a FontReader<'a>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    pub const fn with_glyph_index<const N: usize>(mut self, index: &'a GlyphIndex<N>) -> Self {
+        assert!(
+            index.matches(self.data.0),
+            "The glyph index was created from a different font"
+        );
+        self.glyph_index = index.get();
+        self
+    }
+
+    pub const fn with_kerning(mut self, kerning: &'a [KerningPair]) -> Self {
+        let mut i = 1;
+        while i < kerning.len() {
+            let previous = &kerning[i - 1];
+            let current = &kerning[i];
+            assert!(
+                (previous.first as u32) < (current.first as u32)
+                    || (previous.first as u32 == current.first as u32
+                        && (previous.second as u32) < (current.second as u32)),
+                "Kerning pairs have to be sorted and unique"
+            );
+            i += 1;
+        }
+        self.kerning = DebugIgnore(kerning);
+        self
+    }
+
+    pub const fn with_letter_spacing(mut self, letter_spacing: i32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    pub const fn with_word_spacing(mut self, word_spacing: i32) -> Self {
+        self.word_spacing = word_spacing;
+        self
+    }
+
+    pub const fn with_synthetic_bold(mut self, bold: bool) -> Self {
+        self.synthetic_bold = bold;
+        self
+    }
+
+    pub const fn with_synthetic_italic(mut self, italic: bool) -> Self {
+        self.synthetic_italic = italic;
+        self
+    }
+
+    pub const fn with_fixed_width(mut self, fixed_width: FixedWidth) -> Self {
+        self.fixed_width = fixed_width;
+        self
+    }
+
+    pub const fn with_decorations(mut self, decorations: TextDecorations) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub const fn with_emulated_background(mut self, emulate: bool) -> Self {
+        self.emulate_background = emulate;
+        self
+    }
+
+    /// Resolves the settings that would otherwise have to be looked up for every glyph.
+    ///
+    /// Every rendering and measuring function resolves the font once, before it starts.
+    pub fn resolved(&self) -> Self {
+        let resolve = |width| match width {
+            CellWidth::WidestDigit => self.widest_digit().map(CellWidth::Pixels),
+            width => Some(width),
+        };
+
+        let mut font = self.clone();
+        font.fixed_width = match self.fixed_width {
+            FixedWidth::Proportional => FixedWidth::Proportional,
+            FixedWidth::Digits(width) => {
+                resolve(width).map_or(FixedWidth::Proportional, FixedWidth::Digits)
+            }
+            FixedWidth::AllGlyphs(width) => {
+                resolve(width).map_or(FixedWidth::Proportional, FixedWidth::AllGlyphs)
+            }
+        };
+        font
+    }
+
+    /// Whether a background color can be rendered, either natively or emulated.
+    pub const fn supports_background(&self) -> bool {
+        self.emulate_background || self.supports_native_background()
+    }
+
+    /// Whether this font and all of its fallbacks can render a background color natively.
+    ///
+    /// Box filling a glyph of a transparent-only fallback font would leave gaps.
+    const fn supports_native_background(&self) -> bool {
+        self.supports_background_color
+            && match self.fallback {
+                Some(fallback) => fallback.supports_native_background(),
+                None => true,
+            }
+    }
+
+    /// The row of the underline, relative to the baseline.
+    pub const fn underline_offset(&self) -> i32 {
+        if self.descent < 0 {
+            -(self.descent as i32) / 2
+        } else {
+            0
+        }
+    }
+
+    /// The row of the strikethrough, relative to the baseline.
+    pub const fn strikethrough_offset(&self) -> i32 {
+        if self.ascent > 0 {
+            -(self.ascent as i32 + 1) / 2
+        } else {
+            0
+        }
+    }
+
+    pub const fn info(&self) -> FontInfo {
+        FontInfo {
+            glyph_count: self.glyph_count,
+            supports_background_color: self.supports_background_color,
+            bits_per_0: self.m0,
+            bits_per_1: self.m1,
+            bits_per_char_width: self.bitcnt_w,
+            bits_per_char_height: self.bitcnt_h,
+            bits_per_char_x: self.bitcnt_x,
+            bits_per_char_y: self.bitcnt_y,
+            bits_per_delta_x: self.bitcnt_d,
+            bounding_box_width: self.font_bounding_box_width,
+            bounding_box_height: self.font_bounding_box_height,
+            bounding_box_x_offset: self.font_bounding_box_x_offset,
+            bounding_box_y_offset: self.font_bounding_box_y_offset,
+            ascent: self.ascent,
+            descent: self.descent,
+            ascent_of_parentheses: self.ascent_of_parentheses,
+            descent_of_parentheses: self.descent_of_parentheses,
+            start_pos_upper_a: self.array_offset_upper_a,
+            start_pos_lower_a: self.array_offset_lower_a,
+            start_pos_unicode: self.array_offset_0x0100,
+        }
+    }
+
+    pub const fn transform(&self, origin: Point) -> Transform {
+        Transform::new(origin, self.rotation, self.scale)
+    }
+
     pub const fn get_default_line_height(&self) -> u8 {
         assert!(self.font_bounding_box_height >= 0);
         self.font_bounding_box_height as u8 + 1
     }
 
-    pub fn try_retrieve_glyph_data(&self, ch: char) -> Result<Option<GlyphReader>, LookupError> {
-        match self.retrieve_glyph_data(ch) {
-            Err(LookupError::GlyphNotFound(_)) if self.ignore_unknown_glyphs => Ok(None),
-            Ok(g) => Ok(Some(g)),
-            Err(e) => Err(e),
+    /// The additional advance between `previous` and `ch`.
+    pub fn kerning(&self, previous: Option<char>, ch: char) -> i32 {
+        let Some(previous) = previous else {
+            return 0;
+        };
+
+        // Kerning would break the alignment of the cells
+        if self.cell_width_setting(previous).is_some() || self.cell_width_setting(ch).is_some() {
+            return 0;
+        }
+
+        self.kerning
+            .binary_search_by(|pair| (pair.first, pair.second).cmp(&(previous, ch)))
+            .map_or(0, |pos| self.kerning[pos].adjustment.into())
+    }
+
+    /// Whether this font or one of its fallbacks contains the glyph.
+    pub fn has_glyph(&self, ch: char) -> bool {
+        self.retrieve_glyph_data(ch).is_ok()
+    }
+
+    /// The additional advance after `ch`.
+    pub fn spacing(&self, ch: char) -> i32 {
+        if ch == ' ' {
+            self.letter_spacing + self.word_spacing
+        } else {
+            self.letter_spacing
         }
     }
 
-    fn retrieve_glyph_data(&self, ch: char) -> Result<GlyphReader, LookupError> {
+    pub fn try_retrieve_glyph_data(
+        &self,
+        ch: char,
+    ) -> Result<Option<GlyphReader<'a>>, LookupError> {
+        let glyph = match self.retrieve_glyph_data(ch) {
+            Err(LookupError::GlyphNotFound(_)) if self.replacement_char.is_some() => {
+                let replacement = self
+                    .replacement_char
+                    .and_then(|replacement| self.retrieve_glyph_data(replacement).ok());
+                replacement.unwrap_or_else(|| GlyphReader::new_tofu(self))
+            }
+            Err(LookupError::GlyphNotFound(_)) if self.ignore_unknown_glyphs => return Ok(None),
+            Ok(g) => g,
+            Err(e) => return Err(e),
+        };
+
+        let glyph = glyph.with_synthetic_style(self.synthetic_bold, self.synthetic_italic);
+        Ok(Some(match self.cell_width(ch) {
+            Some(width) => glyph.with_cell_width(width),
+            None => glyph,
+        }))
+    }
+
+    /// The configured width of the cell of `ch`, if it gets rendered in one.
+    fn cell_width_setting(&self, ch: char) -> Option<CellWidth> {
+        match self.fixed_width {
+            FixedWidth::Proportional => None,
+            FixedWidth::Digits(width) => ch.is_ascii_digit().then_some(width),
+            FixedWidth::AllGlyphs(width) => Some(width),
+        }
+    }
+
+    /// The width of the cell of `ch`, if it gets rendered in one.
+    fn cell_width(&self, ch: char) -> Option<i32> {
+        match self.cell_width_setting(ch)? {
+            CellWidth::Pixels(width) => Some(width.into()),
+            // Only reached by fonts that didn't get resolved
+            CellWidth::WidestDigit => self.widest_digit().map(i32::from),
+        }
+    }
+
+    /// The largest advance of the digits `0` to `9`, including synthetic styles.
+    fn widest_digit(&self) -> Option<u8> {
+        ('0'..='9')
+            .filter_map(|digit| self.retrieve_glyph_data(digit).ok())
+            .map(|glyph| {
+                let advance = glyph
+                    .with_synthetic_style(self.synthetic_bold, self.synthetic_italic)
+                    .advance();
+                u8::try_from(advance.max(0)).unwrap_or(u8::MAX)
+            })
+            .max()
+    }
+
+    /// Retrieves the glyph from this font, or from the first fallback font that contains it.
+    fn retrieve_glyph_data(&self, ch: char) -> Result<GlyphReader<'a>, LookupError> {
+        match (self.retrieve_own_glyph_data(ch), self.fallback) {
+            (Err(LookupError::GlyphNotFound(_)), Some(fallback)) => {
+                fallback.retrieve_glyph_data(ch)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn retrieve_own_glyph_data(&self, ch: char) -> Result<GlyphReader<'a>, LookupError> {
         // Retrieve u16 glyph value
         let encoding = u16::try_from(u32::from(ch)).map_err(|_| LookupError::GlyphNotFound(ch))?;
 
@@ -103,7 +410,7 @@ impl FontReader {
                 glyph.jump_by(self.array_offset_upper_a.into());
             }
 
-            while glyph.get_ch() as u16 != encoding {
+            while u16::from(glyph.get_ch().ok_or(LookupError::GlyphNotFound(ch))?) != encoding {
                 glyph
                     .jump_to_next()
                     .then_some(())
@@ -114,15 +421,20 @@ impl FontReader {
         } else {
             let (mut glyph, unicode_jump_table) = glyph.into_unicode_mode(self.array_offset_0x0100);
 
-            let jump_offset = unicode_jump_table
-                .calculate_jump_offset(encoding)
-                .ok_or(LookupError::GlyphNotFound(ch))?;
+            let jump_offset = match &self.glyph_index {
+                Some(glyph_index) => glyph_index.calculate_jump_offset(encoding),
+                None => unicode_jump_table.calculate_jump_offset(encoding),
+            }
+            .ok_or(LookupError::GlyphNotFound(ch))?;
 
             glyph.jump_by(jump_offset);
 
+            // Indexed fonts are known to be sorted, so the search can stop early
+            let is_sorted = self.glyph_index.is_some();
+
             loop {
-                let glyph_ch = glyph.get_ch();
-                if glyph_ch == 0 {
+                let glyph_ch = glyph.get_ch().unwrap_or(0);
+                if glyph_ch == 0 || (is_sorted && glyph_ch > encoding) {
                     return Err(LookupError::GlyphNotFound(ch));
                 }
                 if glyph_ch == encoding {
@@ -182,12 +494,182 @@ mod tests {
             array_offset_lower_a: 0,
             array_offset_0x0100: 2,
             ignore_unknown_glyphs: false,
+            replacement_char: None,
             line_height: 3,
+            rotation: FontRotation::Rotate0,
+            scale: 1,
+            fallback: None,
+            glyph_index: None,
+            kerning: DebugIgnore(&[]),
+            letter_spacing: 0,
+            word_spacing: 0,
+            synthetic_bold: false,
+            synthetic_italic: false,
+            fixed_width: FixedWidth::Proportional,
+            decorations: TextDecorations::NONE,
+            emulate_background: false,
         };
 
         assert_eq!(format!("{:?}", font), format!("{:?}", expected));
     }
 
+    #[test]
+    fn from_data_matches_new() {
+        assert_eq!(
+            format!("{:?}", FontReader::from_data(TestFont::DATA)),
+            format!("{:?}", FontReader::new::<TestFont>())
+        );
+    }
+
+    #[test]
+    fn retrieves_missing_glyphs_from_fallback() {
+        let fallback = FontReader::new::<crate::fonts::u8g2_font_u8glib_4_tf>();
+        let font = FontReader::new::<TestFont>().with_fallback(&fallback);
+
+        assert!(FontReader::new::<TestFont>()
+            .retrieve_glyph_data('a')
+            .is_err());
+        assert!(font.retrieve_glyph_data('a').is_ok());
+        assert!(matches!(
+            font.retrieve_glyph_data('☃'),
+            Err(LookupError::GlyphNotFound('☃'))
+        ));
+    }
+
+    #[test]
+    fn replaces_unknown_glyphs() {
+        let font = FontReader::new::<crate::fonts::u8g2_font_u8glib_4_tf>()
+            .with_ignore_unknown_glyphs(true)
+            .with_replacement_char(Some('a'));
+        let replacement = font.try_retrieve_glyph_data('☃').unwrap().unwrap();
+        assert!(!replacement.is_tofu());
+        assert_eq!(
+            replacement.size(),
+            font.retrieve_glyph_data('a').unwrap().size()
+        );
+
+        let font = font.with_replacement_char(Some('☃'));
+        let tofu = font.try_retrieve_glyph_data('☃').unwrap().unwrap();
+        assert!(tofu.is_tofu());
+        assert_eq!(tofu.size().width, font.font_bounding_box_width as u32);
+        assert_eq!(tofu.size().height, font.font_bounding_box_height as u32);
+
+        let font = font.with_replacement_char(None);
+        assert!(font.try_retrieve_glyph_data('☃').unwrap().is_none());
+    }
+
+    #[test]
+    fn looks_up_kerning_pairs() {
+        let kerning = [
+            KerningPair::new('A', 'V', -2),
+            KerningPair::new('T', 'o', 1),
+            KerningPair::new('V', 'A', -3),
+        ];
+        let font = FontReader::new::<TestFont>().with_kerning(&kerning);
+
+        assert_eq!(font.kerning(Some('A'), 'V'), -2);
+        assert_eq!(font.kerning(Some('V'), 'A'), -3);
+        assert_eq!(font.kerning(Some('T'), 'o'), 1);
+        assert_eq!(font.kerning(Some('o'), 'T'), 0);
+        assert_eq!(font.kerning(None, 'V'), 0);
+        assert_eq!(FontReader::new::<TestFont>().kerning(Some('A'), 'V'), 0);
+    }
+
+    #[test]
+    fn applies_synthetic_styles() {
+        let fallback = FontReader::new::<crate::fonts::u8g2_font_u8glib_4_tf>();
+        let font = FontReader::new::<crate::fonts::u8g2_font_6x13_tr>().with_fallback(&fallback);
+        let plain = font.try_retrieve_glyph_data('l').unwrap().unwrap();
+
+        let bold = font.clone().with_synthetic_bold(true);
+        let glyph = bold.try_retrieve_glyph_data('l').unwrap().unwrap();
+        assert_eq!(glyph.size().width, plain.size().width + 1);
+        assert_eq!(glyph.advance(), plain.advance() + 1);
+        assert_eq!(glyph.left(0), plain.left(0));
+
+        let italic = font.clone().with_synthetic_italic(true);
+        let glyph = italic.try_retrieve_glyph_data('l').unwrap().unwrap();
+        assert!(glyph.size().width > plain.size().width);
+        assert_eq!(glyph.size().height, plain.size().height);
+        assert_eq!(glyph.italic_shift(plain.size().height as i32 - 1), 0);
+        assert!(glyph.italic_shift(0) > 0);
+        // The right side bearing stays the same
+        let right_bearing = |g: &GlyphReader| g.advance() - g.left(0) - g.size().width as i32;
+        assert!(glyph.advance() > plain.advance());
+        assert_eq!(right_bearing(&glyph), right_bearing(&plain));
+
+        let glyph = bold.try_retrieve_glyph_data('\u{a1}').unwrap().unwrap();
+        assert!(glyph.is_bold());
+    }
+
+    #[test]
+    fn renders_glyphs_in_cells() {
+        let kerning = [
+            KerningPair::new('1', 'A', -2),
+            KerningPair::new('A', 'V', -2),
+        ];
+        let font = FontReader::new::<crate::fonts::u8g2_font_helvR08_tr>().with_kerning(&kerning);
+        let plain = font.try_retrieve_glyph_data('1').unwrap().unwrap();
+        let widest = font.try_retrieve_glyph_data('0').unwrap().unwrap();
+
+        let tabular = font
+            .clone()
+            .with_fixed_width(FixedWidth::Digits(CellWidth::WidestDigit));
+        let glyph = tabular.try_retrieve_glyph_data('1').unwrap().unwrap();
+        assert_eq!(glyph.advance(), widest.advance());
+        assert_eq!(
+            glyph.left(0),
+            plain.left(0) + (widest.advance() - plain.advance()) / 2
+        );
+        assert_eq!(glyph.size(), plain.size());
+        assert_eq!(tabular.kerning(Some('1'), 'A'), 0);
+        assert_eq!(tabular.kerning(Some('A'), 'V'), -2);
+
+        // The widest digit only gets looked up once
+        let resolved = tabular.resolved();
+        let cell = u8::try_from(widest.advance()).unwrap();
+        assert_eq!(
+            resolved.fixed_width,
+            FixedWidth::Digits(CellWidth::Pixels(cell))
+        );
+        let glyph = resolved.try_retrieve_glyph_data('1').unwrap().unwrap();
+        assert_eq!(glyph.advance(), widest.advance());
+        let no_digits = FontReader::new::<crate::fonts::u8g2_font_open_iconic_all_1x_t>()
+            .with_fixed_width(FixedWidth::AllGlyphs(CellWidth::WidestDigit));
+        assert!(!no_digits.has_glyph('0'));
+        assert_eq!(no_digits.resolved().fixed_width, FixedWidth::Proportional);
+
+        let bold = tabular.with_synthetic_bold(true);
+        let glyph = bold.try_retrieve_glyph_data('1').unwrap().unwrap();
+        assert_eq!(glyph.advance(), widest.advance() + 1);
+
+        let monospace = font.with_fixed_width(FixedWidth::AllGlyphs(CellWidth::Pixels(9)));
+        let glyph = monospace.try_retrieve_glyph_data('A').unwrap().unwrap();
+        assert_eq!(glyph.advance(), 9);
+        assert_eq!(monospace.kerning(Some('A'), 'V'), 0);
+    }
+
+    #[test]
+    fn computes_spacing() {
+        let font = FontReader::new::<TestFont>()
+            .with_letter_spacing(2)
+            .with_word_spacing(-3);
+
+        assert_eq!(font.spacing('a'), 2);
+        assert_eq!(font.spacing(' '), -1);
+        assert_eq!(FontReader::new::<TestFont>().spacing(' '), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_duplicate_kerning_pairs() {
+        let kerning = [
+            KerningPair::new('A', 'V', -2),
+            KerningPair::new('A', 'V', -3),
+        ];
+        let _ = FontReader::new::<TestFont>().with_kerning(&kerning);
+    }
+
     #[test]
     fn can_handle_unicode_next_is_zero() {
         // This test is specifically engineered to test an error path that doesn't happen
@@ -3,12 +3,12 @@ pub struct UnicodeJumptableEntry {
     character_upper_limit: u16,
 }
 
-pub struct UnicodeJumptableReader {
-    data: &'static [u8],
+pub struct UnicodeJumptableReader<'a> {
+    data: &'a [u8],
 }
 
-impl UnicodeJumptableReader {
-    pub fn new(data: &'static [u8]) -> Self {
+impl<'a> UnicodeJumptableReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
         Self { data }
     }
 
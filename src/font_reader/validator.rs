@@ -0,0 +1,251 @@
+use crate::{font_reader::U8G2_FONT_DATA_STRUCT_SIZE, FontDataError};
+
+/// Checks that font data is structurally sound, so that
+/// decoding it later can neither panic nor loop forever.
+///
+/// This does not check the glyph bitmaps themselves; the glyph decoder
+/// is robust against garbage as long as the tables it navigates are intact.
+pub fn validate(data: &[u8]) -> Result<(), FontDataError> {
+    let header = data
+        .get(..U8G2_FONT_DATA_STRUCT_SIZE)
+        .ok_or(FontDataError::InvalidHeader)?;
+
+    // m0, m1, bitcnt_w, bitcnt_h, bitcnt_x, bitcnt_y, bitcnt_d
+    if !header[2..=8].iter().all(|bits| (1..=8).contains(bits)) {
+        return Err(FontDataError::InvalidHeader);
+    }
+
+    // Font bounding box width and height
+    if (header[9] as i8) < 0 || (header[10] as i8) < 0 {
+        return Err(FontDataError::InvalidHeader);
+    }
+
+    let array_offset_upper_a = usize::from(u16::from_be_bytes([header[17], header[18]]));
+    let array_offset_lower_a = usize::from(u16::from_be_bytes([header[19], header[20]]));
+    let array_offset_0x0100 = usize::from(u16::from_be_bytes([header[21], header[22]]));
+
+    let glyphs = &data[U8G2_FONT_DATA_STRUCT_SIZE..];
+
+    validate_ascii_glyphs(glyphs, array_offset_upper_a, array_offset_lower_a)?;
+
+    let unicode_section = glyphs
+        .get(array_offset_0x0100..)
+        .ok_or(FontDataError::InvalidUnicodeTable)?;
+    validate_unicode_glyphs(unicode_section)
+}
+
+/// Walks the ASCII glyph list, which is terminated by an entry with offset zero.
+fn validate_ascii_glyphs(
+    glyphs: &[u8],
+    array_offset_upper_a: usize,
+    array_offset_lower_a: usize,
+) -> Result<(), FontDataError> {
+    let mut pos = 0;
+    let mut found_upper_a = false;
+    let mut found_lower_a = false;
+
+    loop {
+        found_upper_a |= pos == array_offset_upper_a;
+        found_lower_a |= pos == array_offset_lower_a;
+
+        let offset = *glyphs
+            .get(pos + 1)
+            .ok_or(FontDataError::InvalidGlyphTable)?;
+        if offset == 0 {
+            break;
+        }
+        pos += usize::from(offset);
+    }
+
+    if found_upper_a && found_lower_a {
+        Ok(())
+    } else {
+        Err(FontDataError::InvalidGlyphTable)
+    }
+}
+
+/// Walks the unicode jump table and the unicode glyph list in lockstep,
+/// making sure every jump lands on the start of a glyph or on the end marker.
+fn validate_unicode_glyphs(unicode_section: &[u8]) -> Result<(), FontDataError> {
+    let read_u16 = |pos: usize| -> Option<u16> {
+        Some(u16::from_be_bytes([
+            *unicode_section.get(pos)?,
+            *unicode_section.get(pos + 1)?,
+        ]))
+    };
+
+    let mut table_pos = 0;
+    let mut jump_target = 0;
+    let mut glyph_pos = None;
+
+    loop {
+        let jump_distance = read_u16(table_pos).ok_or(FontDataError::InvalidUnicodeTable)?;
+        let character_upper_limit =
+            read_u16(table_pos + 2).ok_or(FontDataError::InvalidUnicodeTable)?;
+        table_pos += 4;
+        jump_target += usize::from(jump_distance);
+
+        // The glyphs start directly after the jump table, which we only
+        // know once the first target got read.
+        let pos = glyph_pos.get_or_insert(jump_target);
+        while *pos < jump_target {
+            *pos = next_unicode_glyph(unicode_section, *pos)?
+                .ok_or(FontDataError::InvalidUnicodeTable)?;
+        }
+        if *pos != jump_target || *pos < table_pos {
+            return Err(FontDataError::InvalidUnicodeTable);
+        }
+
+        if character_upper_limit == 0xffff {
+            break;
+        }
+    }
+
+    // Walk the remaining glyphs to make sure the list is terminated
+    let mut pos = glyph_pos.unwrap_or(table_pos);
+    while let Some(next_pos) = next_unicode_glyph(unicode_section, pos)? {
+        pos = next_pos;
+    }
+
+    Ok(())
+}
+
+/// Returns the position of the next glyph, or `None` if `pos` is the end marker.
+fn next_unicode_glyph(unicode_section: &[u8], pos: usize) -> Result<Option<usize>, FontDataError> {
+    let encoding = unicode_section
+        .get(pos..pos + 2)
+        .ok_or(FontDataError::InvalidGlyphTable)?;
+    if encoding == [0, 0] {
+        return Ok(None);
+    }
+
+    match unicode_section.get(pos + 2) {
+        Some(&offset) if offset != 0 => Ok(Some(pos + usize::from(offset))),
+        _ => Err(FontDataError::InvalidGlyphTable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::vec::Vec;
+
+    use super::*;
+
+    const HEADER: [u8; 23] = [
+        0, 0, 4, 4, 8, 8, 8, 8, 8, 1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 2,
+    ];
+
+    fn font(ascii: &[u8], unicode: &[u8]) -> Vec<u8> {
+        let mut data = Vec::from(HEADER);
+        data.extend_from_slice(ascii);
+        data.extend_from_slice(unicode);
+        data
+    }
+
+    fn minimal_font() -> Vec<u8> {
+        font(&[0, 0], &[0, 4, 255, 255, 0, 0])
+    }
+
+    #[test]
+    fn accepts_minimal_font() {
+        assert_eq!(validate(&minimal_font()), Ok(()));
+    }
+
+    #[test]
+    fn accepts_font_with_unicode_glyphs() {
+        let data = font(
+            &[0, 0],
+            &[
+                0, 8, 0x21, 0x00, // Jump table entry
+                0, 3, 255, 255, // Jump table end
+                0x21, 0x00, 3, // First glyph
+                0x22, 0x00, 4, 0xab, // Second glyph
+                0, 0, // End marker
+            ],
+        );
+        assert_eq!(validate(&data), Ok(()));
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        assert_eq!(validate(&HEADER[..22]), Err(FontDataError::InvalidHeader));
+    }
+
+    #[test]
+    fn rejects_invalid_bit_widths() {
+        for invalid_width in [0, 9] {
+            for field in 2..=8 {
+                let mut data = minimal_font();
+                data[field] = invalid_width;
+                assert_eq!(validate(&data), Err(FontDataError::InvalidHeader));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_negative_bounding_box() {
+        for field in [9, 10] {
+            let mut data = minimal_font();
+            data[field] = -1i8 as u8;
+            assert_eq!(validate(&data), Err(FontDataError::InvalidHeader));
+        }
+    }
+
+    #[test]
+    fn rejects_unterminated_glyph_table() {
+        let mut data = Vec::from(HEADER);
+        data.extend_from_slice(&[b'a', 5, 0, 0, 0]);
+        assert_eq!(validate(&data), Err(FontDataError::InvalidGlyphTable));
+    }
+
+    #[test]
+    fn rejects_misplaced_ascii_offsets() {
+        let mut data = font(&[b'A', 3, 0, b'B', 0], &[0, 4, 255, 255, 0, 0]);
+        data[22] = 5;
+        assert_eq!(validate(&data), Ok(()));
+
+        data[18] = 1;
+        assert_eq!(validate(&data), Err(FontDataError::InvalidGlyphTable));
+    }
+
+    #[test]
+    fn rejects_truncated_jump_table() {
+        let data = font(&[0, 0], &[0, 4, 255]);
+        assert_eq!(validate(&data), Err(FontDataError::InvalidUnicodeTable));
+    }
+
+    #[test]
+    fn rejects_unicode_offset_out_of_bounds() {
+        let mut data = minimal_font();
+        data[22] = 100;
+        assert_eq!(validate(&data), Err(FontDataError::InvalidUnicodeTable));
+    }
+
+    #[test]
+    fn rejects_jump_into_glyph() {
+        let data = font(
+            &[0, 0],
+            &[
+                0, 9, 0x21, 0x00, // Jump table entry, one byte too far
+                0, 4, 255, 255, // Jump table end
+                0x21, 0x00, 4, 0xab, // First glyph
+                0x22, 0x00, 3, // Second glyph
+                0, 0, // End marker
+            ],
+        );
+        assert_eq!(validate(&data), Err(FontDataError::InvalidUnicodeTable));
+    }
+
+    #[test]
+    fn rejects_jump_into_jump_table() {
+        let data = font(&[0, 0], &[0, 2, 255, 255, 0, 0]);
+        assert_eq!(validate(&data), Err(FontDataError::InvalidUnicodeTable));
+    }
+
+    #[test]
+    fn rejects_missing_end_marker() {
+        let data = font(&[0, 0], &[0, 4, 255, 255, 0x21, 0x00, 3]);
+        assert_eq!(validate(&data), Err(FontDataError::InvalidGlyphTable));
+    }
+}
@@ -49,6 +49,19 @@
 //!
 //! The fonts can also be seen in [this list](crate::fonts).
 //!
+//! Fonts that are not compiled into the program, like fonts stored on external flash,
+//! can be rendered through [`FontRenderer::from_data()`](FontRenderer::from_data).
+//! If the data can't be trusted, [`FontRenderer::try_from_data()`](FontRenderer::try_from_data)
+//! validates it first, so that corrupt fonts produce an error instead of a panic.
+//!
+//! Texts that mix scripts no single font covers can be rendered by chaining fonts
+//! through [`FontRenderer::with_fallback()`](FontRenderer::with_fallback).
+//! Characters that are missing in all of them can be made visible through
+//! [`FontRenderer::with_replacement_char()`](FontRenderer::with_replacement_char).
+//!
+//! Looking up characters in large fonts, like the Chinese ones, can be slow on small
+//! microcontrollers. A [`GlyphIndex`] speeds this up at the cost of some memory.
+//!
 //! ## Content Types
 //!
 //! Once constructed, the [`FontRenderer`] can render [the following objects](Content):
@@ -64,15 +77,69 @@
 //! For more advanced usecases, use the [`FontRenderer::render_aligned()`](FontRenderer::render_aligned) method.
 //! It further allows for horizontal alignment through an additional parameter.
 //!
+//! To fit longer texts into a given area, [`FontRenderer::render_wrapped()`](FontRenderer::render_wrapped)
+//! word-wraps the text into a rectangle and aligns it inside of it, which also enables
+//! [justified](types::WrappedAlignment::Justify) text.
+//! [`FontRenderer::render_truncated()`](FontRenderer::render_truncated) instead shortens a single
+//! line to a maximum width and ends it with an ellipsis.
+//!
+//! The spacing of proportional fonts can be refined through
+//! [kerning pairs](FontRenderer::with_kerning), and text that is too cramped or too loose
+//! can be adjusted through [letter spacing](FontRenderer::with_letter_spacing) and
+//! [word spacing](FontRenderer::with_word_spacing).
+//! To keep changing numbers from jittering, digits or all glyphs can be rendered in
+//! [cells of a fixed width](FontRenderer::with_fixed_width).
+//! Fonts without a bold or italic variant can be rendered with a
+//! [synthetic bold](FontRenderer::with_synthetic_bold) or
+//! [synthetic italic](FontRenderer::with_synthetic_italic) style.
+//! Text can be underlined or struck through with [decorations](FontRenderer::with_decorations).
+//!
+//! ## Rotation and Scaling
+//!
+//! Through [`FontRenderer::with_rotation()`](FontRenderer::with_rotation), text can be rendered in
+//! 90 degree steps, similar to the font direction of U8g2. Positioning and alignment work relative
+//! to the rotated text direction.
+//!
+//! [`FontRenderer::with_scale()`](FontRenderer::with_scale) magnifies every pixel of the font
+//! by an integer factor, which allows for large text without a dedicated large font.
+//!
 //! ## Bounding Box Calculation
 //!
 //! Additional to the [`render()`](FontRenderer::render) and [`render_aligned()`](FontRenderer::render_aligned) methods,
 //! there is also [`get_rendered_dimensions()`](FontRenderer::get_rendered_dimensions) and
-//! [`get_rendered_dimensions_aligned()`](FontRenderer::get_rendered_dimensions_aligned).
+//! [`get_rendered_dimensions_aligned()`](FontRenderer::get_rendered_dimensions_aligned),
+//! as well as [`get_rendered_dimensions_wrapped()`](FontRenderer::get_rendered_dimensions_wrapped)
+//! and [`get_rendered_dimensions_truncated()`](FontRenderer::get_rendered_dimensions_truncated).
 //!
 //! Those functions behave almost identical to their `render` counterparts, but don't actually perform any rendering. This
 //! can be very useful if the dimensions of the text are required for other drawing operations prior to the actual text rendering.
 //!
+//! For the positions of the individual characters, [`layout()`](FontRenderer::layout) iterates over the characters
+//! at exactly the positions [`render_aligned()`](FontRenderer::render_aligned) draws them to.
+//! [`char_index_at()`](FontRenderer::char_index_at) and [`caret_position()`](FontRenderer::caret_position)
+//! build on it to map between points on the display and character indices, like text entry widgets require.
+//!
+//! ## Clipping
+//!
+//! All rendering functions only draw inside of the [bounding box](embedded_graphics_core::geometry::Dimensions::bounding_box)
+//! of the display. Glyphs outside of it get skipped without decoding them, which makes scrolling long texts cheap.
+//! To redraw only a part of the display, render to a clipped draw target, like
+//! [`DrawTargetExt::clipped()`](https://docs.rs/embedded-graphics/latest/embedded_graphics/draw_target/trait.DrawTargetExt.html#tymethod.clipped)
+//! of embedded-graphics. The returned dimensions are not affected by clipping.
+//!
+//! ## Prepared Text
+//!
+//! Static text that gets redrawn often, like the labels of a dashboard, can be laid out once with
+//! [`prepare()`](FontRenderer::prepare). The resulting [`PreparedText`] draws the text to any position
+//! and in any color without searching the font again.
+//!
+//! ## Bitmaps
+//!
+//! Text that gets drawn often can be pre-rendered into a packed 1 bit per pixel buffer with
+//! [`render_to_bitmap()`](FontRenderer::render_to_bitmap). The resulting [`Bitmap`] is an
+//! [`ImageDrawable`](embedded_graphics_core::image::ImageDrawable), which can be drawn in any color
+//! without decoding the glyphs again.
+//!
 //! ## Colors and Backgrounds
 //!
 //! While a foreground color must always be specified for rendering a font, there is also the option to set a background color.
@@ -80,6 +147,13 @@
 //!
 //! Note that many fonts do not actually support rendering with a background color (due to occlusions).
 //! Supplying a background color to a font that doesn't support it causes a [runtime error](crate::Error::BackgroundColorNotSupported).
+//! To use a background color with those fonts anyway, enable [`with_emulated_background()`](FontRenderer::with_emulated_background),
+//! which fills the box behind every line before rendering the glyphs transparently.
+//!
+//! For text on top of busy backgrounds, [`FontColor::WithOutline`](types::FontColor::WithOutline) draws a one pixel wide
+//! outline around every glyph and [`FontColor::WithShadow`](types::FontColor::WithShadow) draws a shadow below it.
+//! The `get_rendered_dimensions` functions don't know the color, so their bounding boxes don't include those effects;
+//! [`get_effect_bounding_box()`](FontRenderer::get_effect_bounding_box) grows them accordingly.
 //!
 //! # Example
 //!
@@ -117,6 +191,7 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod bitmap;
 mod content;
 mod error;
 mod font;
@@ -133,11 +208,19 @@ pub mod fonts;
 /// Data types used in common API functions.
 pub mod types;
 
+pub use bitmap::Bitmap;
 pub use content::Content;
+pub use error::BitmapError;
 pub use error::Error;
+pub use error::FontDataError;
 pub use error::LookupError;
+pub use error::PrepareError;
 pub use font::Font;
+pub use font_reader::Encodings;
+pub use font_reader::GlyphIndex;
 pub use renderer::FontRenderer;
+pub use renderer::Layout;
+pub use renderer::PreparedText;
 
 #[cfg(feature = "embedded_graphics_textstyle")]
 mod u8g2_text_style;
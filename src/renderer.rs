@@ -1,29 +1,53 @@
+use core::{cell::RefCell, cmp};
+
 use embedded_graphics_core::{
+    pixelcolor::{BinaryColor, PixelColor},
     prelude::{DrawTarget, Point, Size},
     primitives::Rectangle,
 };
 
 use crate::{
+    bitmap::{Bitmap, BitmapTarget},
     content::{
-        vertical_offset::compute_vertical_offset_from_static_newlines, LineDimensionsIterator,
+        truncated_line::compute_truncation,
+        vertical_offset::compute_vertical_offset_from_static_newlines,
+        wrapped_lines::{count_wrapped_lines, WrappedLine, WrappedLinesIterator},
+        LineDimensionsIterator,
+    },
+    error::DrawError,
+    font_reader::{Encodings, FontReader},
+    types::{
+        FixedWidth, FontColor, FontInfo, FontRotation, GlyphMetrics, HorizontalAlignment,
+        KerningPair, RenderedDimensions, RenderedTruncatedDimensions, RenderedWrappedDimensions,
+        TextDecorations, VerticalPosition, WrappedAlignment,
     },
-    font_reader::FontReader,
-    types::{FontColor, HorizontalAlignment, RenderedDimensions, VerticalPosition},
-    utils::{combine_bounding_boxes, HorizontalRenderedDimensions},
-    Content, Error, Font, LookupError,
+    utils::{combine_bounding_boxes, HorizontalRenderedDimensions, Transform},
+    BitmapError, Content, Error, Font, FontDataError, GlyphIndex, LookupError, PrepareError,
 };
 
-use self::render_actions::{compute_glyph_dimensions, compute_horizontal_offset, render_glyph};
+use self::render_actions::{
+    compute_glyph_dimensions, compute_horizontal_offset, compute_wrapped_text_anchor,
+    fill_line_background, grow_by_effect, has_effect, layers, render_glyph,
+    split_emulated_background, Layer,
+};
 
+mod layout;
+mod prepared_text;
 pub mod render_actions;
 
+pub use self::{layout::Layout, prepared_text::PreparedText};
+
 /// Renders text of a specific [`Font`] to a [`DrawTarget`].
+///
+/// The lifetime `'a` is the lifetime of the font data. It is `'static` for all
+/// [built-in fonts](crate::fonts) and only matters for fonts loaded at runtime
+/// through [`from_data()`](FontRenderer::from_data) or [`try_from_data()`](FontRenderer::try_from_data).
 #[derive(Debug, Clone)]
-pub struct FontRenderer {
-    font: FontReader,
+pub struct FontRenderer<'a> {
+    font: FontReader<'a>,
 }
 
-impl FontRenderer {
+impl FontRenderer<'static> {
     /// Creates a new instance of a font renderer.
     ///
     /// # Generics
@@ -35,6 +59,70 @@ impl FontRenderer {
             font: FontReader::new::<FONT>(),
         }
     }
+}
+
+impl<'a> FontRenderer<'a> {
+    /// Creates a new instance of a font renderer from raw font data.
+    ///
+    /// This allows rendering fonts that are not compiled into the program,
+    /// like fonts that get loaded from external flash or received through an update.
+    ///
+    /// The data has to be in the U8g2 font format, identical to the
+    /// data of the fonts in [`fonts`](crate::fonts).
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw font data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is too short to contain a font header.
+    ///
+    /// Apart from that, the data is not validated. Rendering with corrupt font data
+    /// produces garbage or panics; use [`try_from_data()`](FontRenderer::try_from_data)
+    /// for untrusted data.
+    pub const fn from_data(data: &'a [u8]) -> Self {
+        Self {
+            font: FontReader::from_data(data),
+        }
+    }
+
+    /// Creates a new instance of a font renderer from untrusted raw font data.
+    ///
+    /// Validates the font header, the glyph table and the unicode jump table
+    /// before use. Rendering with a font that passed validation never panics;
+    /// corrupt glyph bitmaps only produce garbage pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw font data.
+    ///
+    /// # Return
+    ///
+    /// The font renderer, or a [`FontDataError`] describing why the data got rejected.
+    pub fn try_from_data(data: &'a [u8]) -> Result<Self, FontDataError> {
+        Ok(Self {
+            font: FontReader::try_from_data(data)?,
+        })
+    }
+
+    /// Attaches a [`GlyphIndex`] to speed up the lookup of unicode glyphs.
+    ///
+    /// This is mainly useful for large fonts, like Chinese or Japanese ones.
+    /// Rendering results are identical with and without index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index, created from the same font as this renderer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index was created from a different font, detected by
+    /// comparing the font size, the font header and the sampled glyphs.
+    pub const fn with_glyph_index<const N: usize>(mut self, index: &'a GlyphIndex<N>) -> Self {
+        self.font = self.font.with_glyph_index(index);
+        self
+    }
 
     /// Switches the font rendering mode to ignore all unrenderable characters
     /// instead of raising an error.
@@ -49,12 +137,32 @@ impl FontRenderer {
         self
     }
 
+    /// Renders a replacement character in place of all unrenderable characters,
+    /// so that missing glyphs stay visible instead of raising an error or being dropped.
+    ///
+    /// The replacement is searched in this font and its [fallbacks](FontRenderer::with_fallback).
+    /// If none of them contains it, a hollow box the size of the font bounding box is drawn instead.
+    ///
+    /// Takes precedence over [`with_ignore_unknown_chars()`](FontRenderer::with_ignore_unknown_chars).
+    /// All dimension calculations take the replacement into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - The character to render instead of unknown characters, or `None` to disable replacement.
+    pub const fn with_replacement_char(mut self, replacement: Option<char>) -> Self {
+        self.font = self.font.with_replacement_char(replacement);
+        self
+    }
+
     /// Sets the line height.
     ///
     /// The line height is defined as the vertical distance between the baseline of two adjacent lines in pixels.
     ///
     /// If the line height is not set explicitly, it will default to the value returned by [`get_default_line_height()`](FontRenderer::get_default_line_height).
     ///
+    /// If the renderer is [scaled](FontRenderer::with_scale), the line height is specified in unscaled pixels
+    /// and gets scaled together with the rest of the text.
+    ///
     /// # Arguments
     ///
     /// * `line_height` - The desired line height, in pixels.
@@ -63,12 +171,267 @@ impl FontRenderer {
         self
     }
 
+    /// Sets the rotation of the rendered text.
+    ///
+    /// The text gets rotated around the position it is rendered to.
+    /// This affects all rendering and dimension computation functions;
+    /// alignment and multi-line layout happen along the rotated text direction,
+    /// and returned advances and bounding boxes are rotated accordingly.
+    ///
+    /// This is equivalent to `setFontDirection` in U8g2.
+    ///
+    /// # Arguments
+    ///
+    /// * `rotation` - The desired rotation.
+    pub const fn with_rotation(mut self, rotation: FontRotation) -> Self {
+        self.font = self.font.with_rotation(rotation);
+        self
+    }
+
+    /// Magnifies the rendered text by an integer factor.
+    ///
+    /// Every pixel of the font gets rendered as a `scale`×`scale` block.
+    /// Advances, line heights and all returned dimensions scale accordingly,
+    /// so alignment keeps working as expected. The font metrics like
+    /// [`get_ascent()`](FontRenderer::get_ascent) stay unscaled; their scaled
+    /// counterparts, like [`get_scaled_ascent()`](FontRenderer::get_scaled_ascent), include the scale.
+    ///
+    /// The default scale is `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - The magnification factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is zero.
+    pub const fn with_scale(mut self, scale: u32) -> Self {
+        self.font = self.font.with_scale(scale);
+        self
+    }
+
+    /// Adjusts the spacing between specific pairs of characters.
+    ///
+    /// Proportional fonts often look uneven for some character combinations, like `"AV"` or `"To"`.
+    /// Kerning pairs move the second character closer to or further away from the first one.
+    /// They only apply to characters that follow each other directly on the same line,
+    /// and all rendering and dimension calculation functions take them into account.
+    ///
+    /// The kerning pairs apply to this font and its [fallbacks](FontRenderer::with_fallback).
+    ///
+    /// # Arguments
+    ///
+    /// * `kerning` - The kerning pairs, sorted by [`first`](KerningPair::first) and then by [`second`](KerningPair::second).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the kerning pairs are not sorted or contain a pair twice.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use u8g2_fonts::{fonts, types::KerningPair, FontRenderer};
+    /// static KERNING: [KerningPair; 3] = [
+    ///     KerningPair::new('A', 'V', -2),
+    ///     KerningPair::new('T', 'o', -2),
+    ///     KerningPair::new('V', 'A', -2),
+    /// ];
+    ///
+    /// static FONT: FontRenderer =
+    ///     FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&KERNING);
+    /// ```
+    pub const fn with_kerning(mut self, kerning: &'a [KerningPair]) -> Self {
+        self.font = self.font.with_kerning(kerning);
+        self
+    }
+
+    /// Adds a fixed number of pixels after every character.
+    ///
+    /// This makes text that looks too cramped or too loose more readable.
+    /// Negative values move the characters closer together.
+    /// The spacing becomes part of the advance of every character, so all rendering
+    /// and dimension calculation functions take it into account.
+    ///
+    /// The letter spacing applies to this font and its [fallbacks](FontRenderer::with_fallback).
+    ///
+    /// The default letter spacing is `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `letter_spacing` - The additional advance after every character, in font pixels.
+    pub const fn with_letter_spacing(mut self, letter_spacing: i32) -> Self {
+        self.font = self.font.with_letter_spacing(letter_spacing);
+        self
+    }
+
+    /// Adds a fixed number of pixels after every space character.
+    ///
+    /// Works like [`with_letter_spacing()`](FontRenderer::with_letter_spacing), but only widens
+    /// the gaps between words. Both settings add up for space characters.
+    ///
+    /// The default word spacing is `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `word_spacing` - The additional advance after every space character, in font pixels.
+    pub const fn with_word_spacing(mut self, word_spacing: i32) -> Self {
+        self.font = self.font.with_word_spacing(word_spacing);
+        self
+    }
+
+    /// Emulates a bold font by drawing every glyph a second time, one pixel to the right.
+    ///
+    /// Useful for font families that don't have a bold variant at the required size.
+    /// The bounding box and the advance of every glyph grow by one pixel.
+    ///
+    /// The style applies to this font and its [fallbacks](FontRenderer::with_fallback).
+    ///
+    /// # Arguments
+    ///
+    /// * `bold` - Whether to render a synthetic bold style.
+    pub const fn with_synthetic_bold(mut self, bold: bool) -> Self {
+        self.font = self.font.with_synthetic_bold(bold);
+        self
+    }
+
+    /// Emulates an italic font by shearing every glyph.
+    ///
+    /// Every row of a glyph gets shifted to the right proportionally to its distance
+    /// from the baseline; descenders get shifted to the left. The bounding boxes grow
+    /// accordingly, and the advance of every glyph grows by how far its top row got shifted,
+    /// so that slanted glyphs don't run into the following ones.
+    ///
+    /// The style applies to this font and its [fallbacks](FontRenderer::with_fallback).
+    ///
+    /// # Arguments
+    ///
+    /// * `italic` - Whether to render a synthetic italic style.
+    pub const fn with_synthetic_italic(mut self, italic: bool) -> Self {
+        self.font = self.font.with_synthetic_italic(italic);
+        self
+    }
+
+    /// Renders glyphs in cells of a fixed width, like a monospace font.
+    ///
+    /// Every affected glyph gets centered in its cell, and the width of the cell replaces
+    /// its advance. Kerning doesn't apply next to these glyphs. This keeps changing numbers,
+    /// like counters or clocks, from jittering horizontally. All rendering and dimension
+    /// calculation functions take the cells into account.
+    ///
+    /// The cells apply to this font and its [fallbacks](FontRenderer::with_fallback).
+    ///
+    /// The default is [`FixedWidth::Proportional`].
+    ///
+    /// # Arguments
+    ///
+    /// * `fixed_width` - The glyphs to render in cells, and the width of the cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use u8g2_fonts::{fonts, types::{CellWidth, FixedWidth}, FontRenderer};
+    /// static FONT: FontRenderer = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+    ///     .with_fixed_width(FixedWidth::Digits(CellWidth::WidestDigit));
+    /// ```
+    pub const fn with_fixed_width(mut self, fixed_width: FixedWidth) -> Self {
+        self.font = self.font.with_fixed_width(fixed_width);
+        self
+    }
+
+    /// Draws lines along the text, like an underline or a strikethrough.
+    ///
+    /// The lines span the advance of every character, including spaces, and get drawn
+    /// in the font color. The underline sits below the baseline, depending on the descent
+    /// of the font; the strikethrough sits at half of the ascent of the font.
+    /// The returned bounding boxes include the lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `decorations` - The lines to draw.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use u8g2_fonts::{fonts, types::TextDecorations, FontRenderer};
+    /// static FONT: FontRenderer = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+    ///     .with_decorations(TextDecorations::NONE.with_underline(true));
+    /// ```
+    pub const fn with_decorations(mut self, decorations: TextDecorations) -> Self {
+        self.font = self.font.with_decorations(decorations);
+        self
+    }
+
+    /// Enables rendering a background color with fonts that don't support it.
+    ///
+    /// Most fonts are transparent-only and return [`Error::BackgroundColorNotSupported`]
+    /// when rendered with [`FontColor::WithBackground`]. With the emulation enabled,
+    /// the box behind every line of text gets filled with the background color first,
+    /// and the glyphs get rendered transparently on top of it. This works with every font,
+    /// including the ones that support a background color natively.
+    ///
+    /// Horizontally, the box spans the advance and the bounding box of the line;
+    /// vertically, it spans the bounding box of the font, like
+    /// [`get_font_bounding_box()`](crate::FontRenderer::get_font_bounding_box).
+    /// The returned dimensions only include the glyphs, not the background box.
+    ///
+    /// # Arguments
+    ///
+    /// * `emulate` - Whether to emulate the background color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use u8g2_fonts::{fonts, FontRenderer};
+    /// static FONT: FontRenderer =
+    ///     FontRenderer::new::<fonts::u8g2_font_helvR08_tr>().with_emulated_background(true);
+    /// ```
+    pub const fn with_emulated_background(mut self, emulate: bool) -> Self {
+        self.font = self.font.with_emulated_background(emulate);
+        self
+    }
+
+    /// Sets a font that renders all characters this font doesn't contain.
+    ///
+    /// The fallback font can have a fallback font itself, which forms a chain of fonts
+    /// that gets searched in order. A character is only reported as missing if none of
+    /// the fonts in the chain contain it.
+    ///
+    /// All glyphs get rendered with the baseline, line height, rotation and scale of
+    /// this renderer; only the glyphs themselves are taken from the fallback fonts.
+    /// A background color can only be rendered natively if every font in the chain
+    /// supports it; otherwise, it has to be [emulated](FontRenderer::with_emulated_background).
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback` - The font to search for characters that are missing in this font.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use u8g2_fonts::{fonts, FontRenderer};
+    /// static CHINESE: FontRenderer = FontRenderer::new::<fonts::u8g2_font_wqy12_t_chinese1>();
+    /// static CYRILLIC: FontRenderer =
+    ///     FontRenderer::new::<fonts::u8g2_font_6x13_t_cyrillic>().with_fallback(&CHINESE);
+    ///
+    /// static FONT: FontRenderer =
+    ///     FontRenderer::new::<fonts::u8g2_font_6x13_tr>().with_fallback(&CYRILLIC);
+    /// ```
+    pub const fn with_fallback(mut self, fallback: &'a FontRenderer<'a>) -> Self {
+        self.font = self.font.with_fallback(&fallback.font);
+        self
+    }
+
     /// Renders text to a display.
     ///
     /// Note that the background color is optional. Omitting it will render
     /// the string with a transparent background.
     ///
-    /// Not every font supports a background color, some fonts require a transparent background.
+    /// Not every font supports a background color, some fonts require a transparent background,
+    /// unless the background gets emulated with
+    /// [`with_emulated_background()`](crate::FontRenderer::with_emulated_background).
+    ///
+    /// Only the part of the text inside of the bounding box of the display gets drawn;
+    /// glyphs outside of it don't even get decoded.
     ///
     /// # Arguments
     ///
@@ -86,7 +449,7 @@ impl FontRenderer {
     pub fn render<Display>(
         &self,
         content: impl Content,
-        mut position: Point,
+        position: Point,
         vertical_pos: VerticalPosition,
         color: FontColor<Display::Color>,
         display: &mut Display,
@@ -94,34 +457,19 @@ impl FontRenderer {
     where
         Display: DrawTarget,
     {
-        let font = &self.font;
-        if color.has_background() && !font.supports_background_color {
+        let font = &self.font.resolved();
+        if color.has_background() && !font.supports_background() {
             return Err(Error::BackgroundColorNotSupported);
         }
 
-        let mut advance = Point::new(0, 0);
-
-        let mut bounding_box = None;
-
-        position.y += content.compute_vertical_offset(font, vertical_pos);
-
-        content.for_each_char(|ch| -> Result<(), Error<Display::Error>> {
-            if ch == '\n' {
-                advance.x = 0;
-                advance.y += i32::try_from(font.line_height).unwrap();
-            } else {
-                let dimensions = render_glyph(ch, position + advance, color, font, display)?;
-                advance += dimensions.advance;
-                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
-            }
-
-            Ok(())
-        })?;
-
-        Ok(RenderedDimensions {
-            advance,
-            bounding_box,
-        })
+        Ok(Self::render_glyphs(
+            font,
+            content,
+            position,
+            vertical_pos,
+            color,
+            display,
+        )?)
     }
 
     /// Renders text to a display with horizontal alignment.
@@ -176,112 +524,507 @@ impl FontRenderer {
         // Therefore we introduce a line_dimensions_iterator that is almost no overhead for
         // glyphs/lines, but makes it possible to implement the format_args case.
 
-        let font = &self.font;
-        if color.has_background() && !font.supports_background_color {
+        let font = &self.font.resolved();
+        if color.has_background() && !font.supports_background() {
             return Err(Error::BackgroundColorNotSupported);
         }
 
+        let transform = font.transform(position);
+
         position.y += content.compute_vertical_offset(font, vertical_pos);
 
         let mut bounding_box = None;
 
-        let mut line_dimensions = content.line_dimensions_iterator();
-        let mut advance = Point::new(
-            compute_horizontal_offset(horizontal_align, line_dimensions.next(font)?),
-            0,
-        );
+        let (color, background) = split_emulated_background(color, font);
 
-        content.for_each_char(|ch| -> Result<(), Error<Display::Error>> {
-            if ch == '\n' {
-                advance.x =
-                    compute_horizontal_offset(horizontal_align, line_dimensions.next(font)?);
-                advance.y += i32::try_from(font.line_height).unwrap();
-            } else {
-                let dimensions = render_glyph(ch, position + advance, color, font, display)?;
-                advance += dimensions.advance;
-                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
-            }
+        for &layer in layers(color) {
+            let mut line_dimensions = content.line_dimensions_iterator();
+            let mut start_line = |advance_y: i32, display: &mut Display| {
+                let line = line_dimensions.next(font)?;
+                let advance = Point::new(
+                    compute_horizontal_offset(horizontal_align, line.clone()),
+                    advance_y,
+                );
+                fill_line_background(
+                    position + advance,
+                    &line,
+                    background,
+                    font,
+                    &transform,
+                    display,
+                )?;
+                Ok::<_, Error<Display::Error>>(advance)
+            };
 
-            Ok(())
-        })?;
+            let mut advance = start_line(0, display)?;
+
+            let mut previous_char = None;
+
+            content.for_each_char(|ch| -> Result<(), Error<Display::Error>> {
+                if ch == '\n' {
+                    advance = start_line(
+                        advance.y + i32::try_from(font.line_height).unwrap(),
+                        display,
+                    )?;
+                    previous_char = None;
+                } else {
+                    advance.x += font.kerning(previous_char.replace(ch), ch);
+                    let dimensions = render_glyph(
+                        ch,
+                        position + advance,
+                        color,
+                        layer,
+                        font,
+                        &transform,
+                        display,
+                    )?;
+                    advance += dimensions.advance;
+                    bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+                }
+
+                Ok(())
+            })?;
+        }
 
-        Ok(bounding_box)
+        Ok(bounding_box.map(|b| transform.rect(b)))
     }
 
-    /// Calculates the dimensions that rendering text with [`render()`](crate::FontRenderer::render) would produce.
+    /// Renders text to a display, word-wrapped to fit into a rectangle.
+    ///
+    /// Lines get wrapped at spaces. A single word that is too long for a line
+    /// gets wrapped between its characters. Newline characters start a new line as usual.
+    ///
+    /// The text is aligned inside of the rectangle. As a rectangle has no baseline,
+    /// [`VerticalPosition::Baseline`] is treated like [`VerticalPosition::Top`].
+    /// Text that doesn't fit vertically overflows the rectangle.
+    ///
+    /// If the renderer is rotated or scaled, the rectangle refers to the area on the
+    /// display; the text gets wrapped at the edge that it runs into.
     ///
     /// # Arguments
     ///
     /// * `content` - The text/character to render.
-    /// * `position` - The position to render to.
-    /// * `vertical_pos` - The vertical positioning.
+    /// * `bounding_box` - The rectangle to render the text into.
+    /// * `vertical_pos` - The vertical positioning inside of the rectangle.
+    /// * `horizontal_align` - The horizontal positioning inside of the rectangle.
+    /// * `color` - The font color.
+    /// * `display` - The display to render to.
     ///
     /// # Return
     ///
-    /// The dimensions of the rendered text.
+    /// The bounding box of the rendered text and the number of lines it got wrapped into.
     ///
-    pub fn get_rendered_dimensions(
+    pub fn render_wrapped<Display>(
         &self,
         content: impl Content,
-        mut position: Point,
+        bounding_box: Rectangle,
         vertical_pos: VerticalPosition,
-    ) -> Result<RenderedDimensions, LookupError> {
-        let font = &self.font;
-
-        let mut advance = Point::new(0, 0);
-
-        let mut bounding_box = None;
-
-        position.y += content.compute_vertical_offset(font, vertical_pos);
+        horizontal_align: WrappedAlignment,
+        color: FontColor<Display::Color>,
+        display: &mut Display,
+    ) -> Result<RenderedWrappedDimensions, Error<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        let font = &self.font.resolved();
+        if color.has_background() && !font.supports_background() {
+            return Err(Error::BackgroundColorNotSupported);
+        }
 
-        content.for_each_char(|ch| -> Result<(), LookupError> {
-            if ch == '\n' {
-                advance.x = 0;
-                advance.y += i32::try_from(font.line_height).unwrap();
-            } else {
-                let dimensions = compute_glyph_dimensions(ch, position + advance, font)?;
-                advance += dimensions.advance;
-                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
-            }
+        let (color, background) = split_emulated_background(color, font);
+        let display = RefCell::new(display);
 
-            Ok(())
-        })?;
+        let render_layer = |layer| {
+            Self::process_wrapped(
+                font,
+                &content,
+                bounding_box,
+                vertical_pos,
+                horizontal_align,
+                |position, line, transform| {
+                    fill_line_background(
+                        position,
+                        line,
+                        background,
+                        font,
+                        transform,
+                        &mut **display.borrow_mut(),
+                    )
+                },
+                |ch, position, transform| {
+                    render_glyph(
+                        ch,
+                        position,
+                        color,
+                        layer,
+                        font,
+                        transform,
+                        &mut **display.borrow_mut(),
+                    )
+                },
+            )
+        };
 
-        Ok(RenderedDimensions {
-            advance,
-            bounding_box,
-        })
+        let effect_box = if has_effect(color) {
+            render_layer(Layer::Effect)?.bounding_box
+        } else {
+            None
+        };
+        let mut dimensions = render_layer(Layer::Foreground)?;
+        dimensions.bounding_box = combine_bounding_boxes(effect_box, dimensions.bounding_box);
+        Ok(dimensions)
     }
 
-    /// Calculates the dimensions that rendering text with
-    /// [`render_aligned()`](crate::FontRenderer::render_aligned) would produce.
+    /// Renders a single line of text, truncated to a maximum width.
+    ///
+    /// If the text doesn't fit, it gets cut after the last character that still fits
+    /// together with an ellipsis. The ellipsis is `'…'` if the font (or one of its fallbacks)
+    /// contains it, `"..."` otherwise; fonts without a `'.'` get truncated without an ellipsis.
+    /// Only the first line of the text gets rendered;
+    /// if there are more, the text counts as truncated as well.
+    ///
+    /// The width is measured like [`get_rendered_dimensions()`](crate::FontRenderer::get_rendered_dimensions)
+    /// does, from `position` to the right edge of the advance or bounding box, whichever is further.
+    /// If the renderer is rotated or scaled, it refers to the distance on the display.
     ///
     /// # Arguments
     ///
     /// * `content` - The text/character to render.
     /// * `position` - The position to render to.
+    /// * `max_width` - The maximum width of the rendered text.
     /// * `vertical_pos` - The vertical positioning.
-    /// * `horizontal_align` - The horizontal alignment.
+    /// * `color` - The font color.
+    /// * `display` - The display to render to.
     ///
     /// # Return
     ///
-    /// The bounding box of the rendered text.
+    /// The dimensions of the rendered text, and whether it got truncated.
     ///
-    pub fn get_rendered_dimensions_aligned(
+    pub fn render_truncated<Display>(
         &self,
         content: impl Content,
-        mut position: Point,
+        position: Point,
+        max_width: u32,
         vertical_pos: VerticalPosition,
-        horizontal_align: HorizontalAlignment,
-    ) -> Result<Option<Rectangle>, LookupError> {
-        let font = &self.font;
+        color: FontColor<Display::Color>,
+        display: &mut Display,
+    ) -> Result<RenderedTruncatedDimensions, Error<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        let font = &self.font.resolved();
+        if color.has_background() && !font.supports_background() {
+            return Err(Error::BackgroundColorNotSupported);
+        }
 
-        position.y += content.compute_vertical_offset(font, vertical_pos);
+        let (color, background) = split_emulated_background(color, font);
+        let display = RefCell::new(display);
 
-        let mut bounding_box = None;
+        let render_layer = |layer| {
+            Self::process_truncated(
+                font,
+                &content,
+                position,
+                max_width,
+                vertical_pos,
+                |position, line, transform| {
+                    fill_line_background(
+                        position,
+                        line,
+                        background,
+                        font,
+                        transform,
+                        &mut **display.borrow_mut(),
+                    )
+                },
+                |ch, position, transform| {
+                    render_glyph(
+                        ch,
+                        position,
+                        color,
+                        layer,
+                        font,
+                        transform,
+                        &mut **display.borrow_mut(),
+                    )
+                },
+            )
+        };
 
-        let mut line_advance = 0;
-        let mut line_bounding_box: Option<Rectangle> = None;
+        let effect_box = if has_effect(color) {
+            render_layer(Layer::Effect)?.bounding_box
+        } else {
+            None
+        };
+        let mut dimensions = render_layer(Layer::Foreground)?;
+        dimensions.bounding_box = combine_bounding_boxes(effect_box, dimensions.bounding_box);
+        Ok(dimensions)
+    }
+
+    /// Calculates the dimensions that rendering text with [`render()`](crate::FontRenderer::render) would produce.
+    ///
+    /// As the color is unknown, the bounding box doesn't include the outline or the shadow of
+    /// [`FontColor::WithOutline`] and [`FontColor::WithShadow`];
+    /// [`get_effect_bounding_box()`](crate::FontRenderer::get_effect_bounding_box) adds them.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to render.
+    /// * `position` - The position to render to.
+    /// * `vertical_pos` - The vertical positioning.
+    ///
+    /// # Return
+    ///
+    /// The dimensions of the rendered text.
+    ///
+    pub fn get_rendered_dimensions(
+        &self,
+        content: impl Content,
+        position: Point,
+        vertical_pos: VerticalPosition,
+    ) -> Result<RenderedDimensions, LookupError> {
+        Self::compute_rendered_dimensions(&self.font.resolved(), &content, position, vertical_pos)
+    }
+
+    /// Grows a measured bounding box by the outline or the shadow of a color.
+    ///
+    /// The measuring functions, like [`get_rendered_dimensions()`](crate::FontRenderer::get_rendered_dimensions)
+    /// or [`layout()`](crate::FontRenderer::layout), don't know the color the text gets rendered in.
+    /// This computes the bounding box that rendering in `color` produces instead,
+    /// taking the rotation and the scaling of the renderer into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounding_box` - The bounding box, as measured without color.
+    /// * `color` - The color the text gets rendered in.
+    ///
+    /// # Return
+    ///
+    /// The bounding box including the effect of the color, or `None` if nothing gets rendered.
+    ///
+    pub fn get_effect_bounding_box<Color: PixelColor>(
+        &self,
+        bounding_box: Option<Rectangle>,
+        color: FontColor<Color>,
+    ) -> Option<Rectangle> {
+        grow_by_effect(bounding_box?, color, &self.font.transform(Point::zero()))
+    }
+
+    /// Renders text into a packed 1 bit per pixel buffer.
+    ///
+    /// The bitmap has the size of the bounding box that
+    /// [`get_rendered_dimensions()`](crate::FontRenderer::get_rendered_dimensions) computes.
+    /// It can then be drawn many times and in any color without decoding the glyphs again,
+    /// or be sent to another device. See [`Bitmap`] for the memory layout.
+    ///
+    /// Pixels of the text are set in the bitmap; all other pixels of it get cleared.
+    /// Bytes of the buffer that don't belong to the bitmap are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to render.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `buffer` - The buffer to render into. It has to be at least
+    ///   [`Bitmap::required_buffer_len()`] bytes long.
+    /// * `stride` - The distance between the starts of two rows in the buffer, in bytes.
+    ///   It has to be at least [`Bitmap::min_stride()`].
+    ///
+    /// # Return
+    ///
+    /// The rendered bitmap. Its [`offset()`](Bitmap::offset) is the position of its
+    /// top left corner, relative to the position the text would be rendered to.
+    ///
+    pub fn render_to_bitmap<'b>(
+        &self,
+        content: impl Content,
+        vertical_pos: VerticalPosition,
+        buffer: &'b mut [u8],
+        stride: usize,
+    ) -> Result<Bitmap<'b>, BitmapError> {
+        let font = &self.font.resolved();
+        let bounding_box =
+            Self::compute_rendered_dimensions(font, &content, Point::zero(), vertical_pos)?
+                .bounding_box
+                .unwrap_or(Rectangle::zero());
+
+        let min_stride = Bitmap::min_stride(bounding_box.size.width);
+        if stride < min_stride {
+            return Err(BitmapError::StrideTooSmall {
+                required: min_stride,
+            });
+        }
+        let required = Bitmap::required_buffer_len(bounding_box.size, stride);
+        if buffer.len() < required {
+            return Err(BitmapError::BufferTooSmall { required });
+        }
+
+        let mut target = BitmapTarget::new(buffer, bounding_box.size, stride);
+        Self::render_glyphs(
+            font,
+            content,
+            -bounding_box.top_left,
+            vertical_pos,
+            FontColor::Transparent(BinaryColor::On),
+            &mut target,
+        )
+        .map_err(|e| match e {
+            DrawError::GlyphNotFound(c) => BitmapError::GlyphNotFound(c),
+            DrawError::DisplayError(e) => match e {},
+        })?;
+
+        Ok(Bitmap::new(
+            target.into_data(),
+            bounding_box.size,
+            stride,
+            bounding_box.top_left,
+        ))
+    }
+
+    /// Renders text without checking whether the font supports the color.
+    fn render_glyphs<Display>(
+        font: &FontReader,
+        content: impl Content,
+        mut position: Point,
+        vertical_pos: VerticalPosition,
+        color: FontColor<Display::Color>,
+        display: &mut Display,
+    ) -> Result<RenderedDimensions, DrawError<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        let transform = font.transform(position);
+
+        let mut advance = Point::new(0, 0);
+
+        let mut bounding_box = None;
+
+        position.y += content.compute_vertical_offset(font, vertical_pos);
+
+        let (color, background) = split_emulated_background(color, font);
+
+        for &layer in layers(color) {
+            advance = Point::new(0, 0);
+
+            // An emulated background has to be filled before the glyphs of each line,
+            // which requires the dimensions of the lines in advance.
+            let mut line_dimensions = background.map(|_| content.line_dimensions_iterator());
+            let mut fill_background =
+                |advance: Point, display: &mut Display| match line_dimensions.as_mut() {
+                    Some(lines) => fill_line_background(
+                        position + advance,
+                        &lines.next(font)?,
+                        background,
+                        font,
+                        &transform,
+                        display,
+                    ),
+                    None => Ok(()),
+                };
+
+            fill_background(advance, display)?;
+
+            let mut previous_char = None;
+
+            content.for_each_char(|ch| -> Result<(), DrawError<Display::Error>> {
+                if ch == '\n' {
+                    advance.x = 0;
+                    advance.y += i32::try_from(font.line_height).unwrap();
+                    previous_char = None;
+                    fill_background(advance, display)?;
+                } else {
+                    advance.x += font.kerning(previous_char.replace(ch), ch);
+                    let dimensions = render_glyph(
+                        ch,
+                        position + advance,
+                        color,
+                        layer,
+                        font,
+                        &transform,
+                        display,
+                    )?;
+                    advance += dimensions.advance;
+                    bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(transform.dimensions(RenderedDimensions {
+            advance,
+            bounding_box,
+        }))
+    }
+
+    fn compute_rendered_dimensions(
+        font: &FontReader,
+        content: &impl Content,
+        mut position: Point,
+        vertical_pos: VerticalPosition,
+    ) -> Result<RenderedDimensions, LookupError> {
+        let transform = font.transform(position);
+
+        let mut advance = Point::new(0, 0);
+
+        let mut bounding_box = None;
+
+        position.y += content.compute_vertical_offset(font, vertical_pos);
+
+        let mut previous_char = None;
+
+        content.for_each_char(|ch| -> Result<(), LookupError> {
+            if ch == '\n' {
+                advance.x = 0;
+                advance.y += i32::try_from(font.line_height).unwrap();
+                previous_char = None;
+            } else {
+                advance.x += font.kerning(previous_char.replace(ch), ch);
+                let dimensions = compute_glyph_dimensions(ch, position + advance, font)?;
+                advance += dimensions.advance;
+                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+            }
+
+            Ok(())
+        })?;
+
+        Ok(transform.dimensions(RenderedDimensions {
+            advance,
+            bounding_box,
+        }))
+    }
+
+    /// Calculates the dimensions that rendering text with
+    /// [`render_aligned()`](crate::FontRenderer::render_aligned) would produce.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to render.
+    /// * `position` - The position to render to.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `horizontal_align` - The horizontal alignment.
+    ///
+    /// # Return
+    ///
+    /// The bounding box of the rendered text.
+    ///
+    pub fn get_rendered_dimensions_aligned(
+        &self,
+        content: impl Content,
+        mut position: Point,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+    ) -> Result<Option<Rectangle>, LookupError> {
+        let font = &self.font.resolved();
+
+        let transform = font.transform(position);
+
+        position.y += content.compute_vertical_offset(font, vertical_pos);
+
+        let mut bounding_box = None;
+
+        let mut line_advance = 0;
+        let mut line_bounding_box: Option<Rectangle> = None;
+        let mut previous_char = None;
         content.for_each_char(|ch| -> Result<(), LookupError> {
             if ch == '\n' {
                 let horizontal_offset = compute_horizontal_offset(
@@ -302,8 +1045,10 @@ impl FontRenderer {
 
                 line_advance = 0;
                 line_bounding_box = None;
+                previous_char = None;
                 position.y += i32::try_from(font.line_height).unwrap();
             } else {
+                line_advance += font.kerning(previous_char.replace(ch), ch);
                 let dimensions = compute_glyph_dimensions(ch, Point::new(line_advance, 0), font)?;
                 line_bounding_box =
                     combine_bounding_boxes(line_bounding_box, dimensions.bounding_box);
@@ -329,12 +1074,405 @@ impl FontRenderer {
             bounding_box = combine_bounding_boxes(bounding_box, Some(line_bounding_box));
         }
 
-        Ok(bounding_box)
+        Ok(bounding_box.map(|b| transform.rect(b)))
+    }
+
+    /// Lays out text like [`render_aligned()`](crate::FontRenderer::render_aligned) would,
+    /// without rendering it.
+    ///
+    /// Yields the position and bounding box of every character, which is useful for
+    /// hit-testing, drawing cursors and selections, or animating single characters.
+    /// Rendering a character on its own to its [`origin`](crate::types::PositionedGlyph::origin) with
+    /// [`VerticalPosition::Baseline`] reproduces the glyph exactly.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to lay out.
+    /// * `position` - The position to render to.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `horizontal_align` - The horizontal alignment.
+    ///
+    /// # Return
+    ///
+    /// An iterator over the characters of the content, in order.
+    /// It stops after the first error.
+    ///
+    pub fn layout<C: Content>(
+        &self,
+        content: C,
+        mut position: Point,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+    ) -> Layout<'a, C> {
+        let font = self.font.resolved();
+
+        let transform = font.transform(position);
+
+        position.y += content.compute_vertical_offset(&font, vertical_pos);
+
+        Layout::new(font, &content, position, transform, horizontal_align)
+    }
+
+    /// Prepares text to be drawn many times.
+    ///
+    /// Looks up all glyphs and lays out the text once, like
+    /// [`render_aligned()`](crate::FontRenderer::render_aligned) would.
+    /// The resulting [`PreparedText`] can then be drawn to any position and in
+    /// any color without searching the font again, which is useful for static labels
+    /// that get redrawn every frame.
+    ///
+    /// # Generics
+    ///
+    /// * `N` - The maximum number of glyphs of the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to prepare.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `horizontal_align` - The horizontal alignment.
+    ///
+    /// # Return
+    ///
+    /// The prepared text.
+    ///
+    pub fn prepare<const N: usize>(
+        &self,
+        content: impl Content,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+    ) -> Result<PreparedText<'a, N>, PrepareError> {
+        PreparedText::new(
+            &self.font.resolved(),
+            content,
+            vertical_pos,
+            horizontal_align,
+        )
+    }
+
+    /// Finds the character that rendering text with
+    /// [`render_aligned()`](crate::FontRenderer::render_aligned) would place at a point.
+    ///
+    /// Every character occupies the area from its origin to the origin of the character
+    /// after it, over the full line height. Useful for mapping touch input onto text;
+    /// to place a caret between two characters, compare the point with the
+    /// [`caret_position()`](crate::FontRenderer::caret_position)s around the found character.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to search.
+    /// * `position` - The position to render to.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `horizontal_align` - The horizontal alignment.
+    /// * `point` - The point on the display.
+    ///
+    /// # Return
+    ///
+    /// The index of the character at the point, or `None` if there is no character at it.
+    ///
+    pub fn char_index_at(
+        &self,
+        content: impl Content,
+        position: Point,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+        point: Point,
+    ) -> Result<Option<usize>, LookupError> {
+        self.layout(content, position, vertical_pos, horizontal_align)
+            .char_index_at(point)
+    }
+
+    /// Calculates where a caret in front of a character gets placed, if the text gets
+    /// rendered with [`render_aligned()`](crate::FontRenderer::render_aligned).
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character the caret is in.
+    /// * `position` - The position to render to.
+    /// * `vertical_pos` - The vertical positioning.
+    /// * `horizontal_align` - The horizontal alignment.
+    /// * `index` - The index of the character in front of which the caret is.
+    ///   Indices past the last character place the caret at the end of the text.
+    ///
+    /// # Return
+    ///
+    /// The position of the caret on the baseline.
+    ///
+    pub fn caret_position(
+        &self,
+        content: impl Content,
+        position: Point,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+        index: usize,
+    ) -> Result<Point, LookupError> {
+        self.layout(content, position, vertical_pos, horizontal_align)
+            .caret_position(index)
+    }
+
+    /// Calculates the dimensions that rendering text with
+    /// [`render_wrapped()`](crate::FontRenderer::render_wrapped) would produce.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to render.
+    /// * `bounding_box` - The rectangle to render the text into.
+    /// * `vertical_pos` - The vertical positioning inside of the rectangle.
+    /// * `horizontal_align` - The horizontal positioning inside of the rectangle.
+    ///
+    /// # Return
+    ///
+    /// The bounding box of the rendered text and the number of lines it got wrapped into.
+    ///
+    pub fn get_rendered_dimensions_wrapped(
+        &self,
+        content: impl Content,
+        bounding_box: Rectangle,
+        vertical_pos: VerticalPosition,
+        horizontal_align: WrappedAlignment,
+    ) -> Result<RenderedWrappedDimensions, LookupError> {
+        let font = &self.font.resolved();
+
+        Self::process_wrapped(
+            font,
+            &content,
+            bounding_box,
+            vertical_pos,
+            horizontal_align,
+            |_, _, _| Ok(()),
+            |ch, position, _| compute_glyph_dimensions(ch, position, font),
+        )
+    }
+
+    /// The shared layout of [`render_wrapped()`](crate::FontRenderer::render_wrapped) and
+    /// [`get_rendered_dimensions_wrapped()`](crate::FontRenderer::get_rendered_dimensions_wrapped).
+    ///
+    /// Calls `process_line` with the text space position and the dimensions of every line
+    /// before its glyphs, and `process_glyph` with the text space position of every glyph
+    /// that has to be rendered.
+    fn process_wrapped<E>(
+        font: &FontReader,
+        content: &impl Content,
+        bounding_box: Rectangle,
+        vertical_pos: VerticalPosition,
+        horizontal_align: WrappedAlignment,
+        mut process_line: impl FnMut(Point, &HorizontalRenderedDimensions, &Transform) -> Result<(), E>,
+        mut process_glyph: impl FnMut(char, Point, &Transform) -> Result<RenderedDimensions, E>,
+    ) -> Result<RenderedWrappedDimensions, E>
+    where
+        E: From<LookupError>,
+    {
+        // Wrap and align in text space, so rotation and scaling work as usual
+        let transform = font.transform(bounding_box.top_left);
+        let text_box = transform.inverse_rect(bounding_box);
+        let max_width = i32::try_from(text_box.size.width).unwrap_or(i32::MAX);
+
+        // The vertical offset depends on the total number of lines,
+        // which requires an additional pass over the content.
+        let line_count = count_wrapped_lines(content, max_width, font)?;
+
+        // Justified lines get stretched to the right
+        let (horizontal_align, justify) = match horizontal_align {
+            WrappedAlignment::Left => (HorizontalAlignment::Left, false),
+            WrappedAlignment::Center => (HorizontalAlignment::Center, false),
+            WrappedAlignment::Right => (HorizontalAlignment::Right, false),
+            WrappedAlignment::Justify => (HorizontalAlignment::Left, true),
+        };
+
+        let (mut position, vertical_pos) =
+            compute_wrapped_text_anchor(text_box, vertical_pos, horizontal_align);
+        position.y += compute_vertical_offset_from_static_newlines(
+            font,
+            vertical_pos,
+            i32::try_from(line_count - 1).unwrap_or(i32::MAX),
+        );
+
+        let mut lines = WrappedLinesIterator::new(content, max_width);
+        let mut line = lines.next(font)?.unwrap_or_else(|| WrappedLine::empty(0));
+        let mut next_line = lines.next(font)?;
+
+        // Justified lines get stretched to the full width
+        let line_dimensions = |line: &WrappedLine| {
+            let mut dimensions = line.dimensions.clone();
+            if justify && !line.paragraph_end && line.gap_count > 0 {
+                dimensions.advance = cmp::max(dimensions.advance, max_width);
+            }
+            dimensions
+        };
+
+        let mut advance = Point::new(
+            compute_horizontal_offset(horizontal_align, line.dimensions.clone()),
+            0,
+        );
+        process_line(position + advance, &line_dimensions(&line), &transform)?;
+
+        let mut bounding_box = None;
+        let mut index = 0;
+        let mut gap = 0;
+        let mut previous_char = None;
+
+        content.for_each_char(|ch| -> Result<(), E> {
+            while let Some(new_line) = next_line.take_if(|l| l.start <= index) {
+                line = new_line;
+                next_line = lines.next(font)?;
+                advance.x = compute_horizontal_offset(horizontal_align, line.dimensions.clone());
+                advance.y += i32::try_from(font.line_height).unwrap();
+                gap = 0;
+                previous_char = None;
+                process_line(position + advance, &line_dimensions(&line), &transform)?;
+            }
+
+            if line.contains(index) {
+                // Stretch the gaps between words; trailing spaces are never part of a line,
+                // so every space that follows a word starts a gap.
+                if justify && ch == ' ' && previous_char.is_some_and(|c| c != ' ') {
+                    advance.x += line.justify_gap_spacing(gap, max_width);
+                    gap += 1;
+                }
+                advance.x += font.kerning(previous_char.replace(ch), ch);
+
+                let dimensions = process_glyph(ch, position + advance, &transform)?;
+                advance += dimensions.advance;
+                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+            }
+
+            index += 1;
+            Ok(())
+        })?;
+
+        Ok(RenderedWrappedDimensions {
+            bounding_box: bounding_box.map(|b| transform.rect(b)),
+            line_count: u32::try_from(line_count).unwrap_or(u32::MAX),
+        })
+    }
+
+    /// Calculates the dimensions that rendering text with
+    /// [`render_truncated()`](crate::FontRenderer::render_truncated) would produce.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to render.
+    /// * `position` - The position to render to.
+    /// * `max_width` - The maximum width of the rendered text.
+    /// * `vertical_pos` - The vertical positioning.
+    ///
+    /// # Return
+    ///
+    /// The dimensions of the rendered text, and whether it got truncated.
+    ///
+    pub fn get_rendered_dimensions_truncated(
+        &self,
+        content: impl Content,
+        position: Point,
+        max_width: u32,
+        vertical_pos: VerticalPosition,
+    ) -> Result<RenderedTruncatedDimensions, LookupError> {
+        let font = &self.font.resolved();
+
+        Self::process_truncated(
+            font,
+            &content,
+            position,
+            max_width,
+            vertical_pos,
+            |_, _, _| Ok(()),
+            |ch, position, _| compute_glyph_dimensions(ch, position, font),
+        )
+    }
+
+    /// The shared layout of [`render_truncated()`](crate::FontRenderer::render_truncated) and
+    /// [`get_rendered_dimensions_truncated()`](crate::FontRenderer::get_rendered_dimensions_truncated).
+    ///
+    /// Calls `process_line` with the text space position and the dimensions of the line
+    /// before its glyphs, and `process_glyph` with the text space position of every glyph
+    /// that has to be rendered.
+    fn process_truncated<E>(
+        font: &FontReader,
+        content: &impl Content,
+        mut position: Point,
+        max_width: u32,
+        vertical_pos: VerticalPosition,
+        mut process_line: impl FnMut(Point, &HorizontalRenderedDimensions, &Transform) -> Result<(), E>,
+        mut process_glyph: impl FnMut(char, Point, &Transform) -> Result<RenderedDimensions, E>,
+    ) -> Result<RenderedTruncatedDimensions, E>
+    where
+        E: From<LookupError>,
+    {
+        // Measure in text space, so rotation and scaling work as usual
+        let transform = font.transform(position);
+        let max_width = i32::try_from(max_width / transform.scale()).unwrap_or(i32::MAX);
+
+        let truncation = compute_truncation(content, max_width, font)?;
+
+        // Only a single line gets rendered
+        position.y += compute_vertical_offset_from_static_newlines(font, vertical_pos, 0);
+
+        process_line(position, &truncation.dimensions, &transform)?;
+
+        let mut advance = Point::new(0, 0);
+        let mut bounding_box = None;
+        let mut previous_char = None;
+
+        let mut process_char = |ch| -> Result<(), E> {
+            advance.x += font.kerning(previous_char.replace(ch), ch);
+            let dimensions = process_glyph(ch, position + advance, &transform)?;
+            advance += dimensions.advance;
+            bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+            Ok(())
+        };
+
+        let mut index = 0;
+        content.for_each_char(|ch| -> Result<(), E> {
+            if index < truncation.visible_chars {
+                process_char(ch)?;
+            }
+            index += 1;
+            Ok(())
+        })?;
+
+        for ch in truncation.ellipsis.chars() {
+            process_char(ch)?;
+        }
+
+        let dimensions = transform.dimensions(RenderedDimensions {
+            advance,
+            bounding_box,
+        });
+
+        Ok(RenderedTruncatedDimensions {
+            advance: dimensions.advance,
+            bounding_box: dimensions.bounding_box,
+            truncated: truncation.truncated,
+        })
+    }
+
+    /// The underline and the strikethrough of a single line of text that spans `advance` pixels,
+    /// as they would get rendered by [`render()`](crate::FontRenderer::render).
+    #[cfg(feature = "embedded_graphics_textstyle")]
+    pub(crate) fn decoration_lines(
+        &self,
+        mut position: Point,
+        vertical_pos: VerticalPosition,
+        advance: i32,
+    ) -> (Option<Rectangle>, Option<Rectangle>) {
+        let font = &self.font;
+        let transform = font.transform(position);
+
+        position.y += compute_vertical_offset_from_static_newlines(font, vertical_pos, 0);
+
+        let line = |offset| {
+            render_actions::decoration_line(position, offset, advance).map(|r| transform.rect(r))
+        };
+        (
+            line(font.underline_offset()),
+            line(font.strikethrough_offset()),
+        )
     }
 
     /// The ascent of the font.
     ///
     /// Usually a positive number.
+    ///
+    /// The value is unscaled; see [`get_scaled_ascent()`](FontRenderer::get_scaled_ascent).
     pub const fn get_ascent(&self) -> i8 {
         self.font.ascent
     }
@@ -342,15 +1480,29 @@ impl FontRenderer {
     /// The descent of the font.
     ///
     /// *IMPORTANT*: This is usually a *negative* number.
+    ///
+    /// The value is unscaled; see [`get_scaled_descent()`](FontRenderer::get_scaled_descent).
     pub const fn get_descent(&self) -> i8 {
         self.font.descent
     }
 
+    /// The ascent of the font, multiplied by the [scale](FontRenderer::with_scale) of the renderer.
+    pub const fn get_scaled_ascent(&self) -> i32 {
+        self.font.ascent as i32 * self.font.scale as i32
+    }
+
+    /// The descent of the font, multiplied by the [scale](FontRenderer::with_scale) of the renderer.
+    ///
+    /// *IMPORTANT*: This is usually a *negative* number.
+    pub const fn get_scaled_descent(&self) -> i32 {
+        self.font.descent as i32 * self.font.scale as i32
+    }
+
     /// The maximum possible bounding box of all glyphs if they were rendered with
     /// [`render()`](crate::FontRenderer::render) at position `(0,0)`.
     pub const fn get_font_bounding_box(&self, vertical_pos: VerticalPosition) -> Rectangle {
         let y_offset = compute_vertical_offset_from_static_newlines(&self.font, vertical_pos, 0);
-        Rectangle {
+        let bounding_box = Rectangle {
             top_left: Point::new(
                 self.font.font_bounding_box_x_offset as i32,
                 y_offset
@@ -361,15 +1513,114 @@ impl FontRenderer {
                 self.font.font_bounding_box_width as u32,
                 self.font.font_bounding_box_height as u32,
             ),
-        }
+        };
+        self.font.transform(Point::new(0, 0)).rect(bounding_box)
     }
 
     /// The default line height.
     ///
     /// The line height is defined as the vertical distance between the baseline of two adjacent lines in pixels.
+    ///
+    /// Like the argument of [`with_line_height()`](FontRenderer::with_line_height), it is unscaled;
+    /// see [`get_scaled_default_line_height()`](FontRenderer::get_scaled_default_line_height)
+    /// for the distance on the display.
     pub const fn get_default_line_height(&self) -> u32 {
         self.font.get_default_line_height() as u32
     }
+
+    /// The default line height, multiplied by the [scale](FontRenderer::with_scale) of the renderer.
+    ///
+    /// This is the distance between the baselines of two adjacent lines on the display.
+    pub const fn get_scaled_default_line_height(&self) -> u32 {
+        self.font.get_default_line_height() as u32 * self.font.scale
+    }
+
+    /// The header of the font.
+    ///
+    /// Contains the raw metadata of the font, independent of the configuration of the renderer.
+    pub const fn get_font_info(&self) -> FontInfo {
+        self.font.info()
+    }
+
+    /// Whether the font or one of its [fallbacks](FontRenderer::with_fallback) contains a character.
+    ///
+    /// Characters for which this returns `false` get replaced, ignored or raise an error
+    /// when rendered, depending on the configuration of the renderer.
+    pub fn has_glyph(&self, ch: char) -> bool {
+        self.font.has_glyph(ch)
+    }
+
+    /// Iterates over all characters the font contains, in the order they are stored in the font.
+    ///
+    /// Doesn't include the characters of [fallback fonts](FontRenderer::with_fallback).
+    pub fn encodings(&self) -> Encodings<'_, 'a> {
+        Encodings::new(&self.font)
+    }
+
+    /// The metrics of the glyph the renderer uses for a character.
+    ///
+    /// These are the same numbers the renderer lays out text with,
+    /// including the [replacement](FontRenderer::with_replacement_char) of unknown characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The character.
+    ///
+    /// # Return
+    ///
+    /// The metrics of the glyph. All zero, if the character gets
+    /// [ignored](FontRenderer::with_ignore_unknown_chars).
+    ///
+    pub fn glyph_metrics(&self, ch: char) -> Result<GlyphMetrics, LookupError> {
+        let font = self.font.resolved();
+        let Some(glyph) = font.try_retrieve_glyph_data(ch)? else {
+            return Ok(GlyphMetrics::default());
+        };
+
+        let metrics = glyph.metrics();
+        let scale = self.font.scale;
+        Ok(GlyphMetrics {
+            width: metrics.width * scale,
+            height: metrics.height * scale,
+            offset_x: metrics.offset_x * scale as i32,
+            offset_y: metrics.offset_y * scale as i32,
+            advance: (metrics.advance + self.font.spacing(ch)) * scale as i32,
+        })
+    }
+
+    /// Reports every character of a text that the font can't render, without rendering it.
+    ///
+    /// Unlike rendering, which stops at the first [`LookupError::GlyphNotFound`],
+    /// this checks the entire text. Like [`has_glyph()`](FontRenderer::has_glyph),
+    /// it considers [fallback fonts](FontRenderer::with_fallback), but no replacement
+    /// or ignoring of unknown characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The text/character to check.
+    /// * `callback` - Gets called with the index and the value of every missing character.
+    ///   The index is the same as the one reported by [`layout()`](FontRenderer::layout).
+    ///
+    /// # Return
+    ///
+    /// The number of missing characters.
+    ///
+    pub fn missing_chars(
+        &self,
+        content: impl Content,
+        mut callback: impl FnMut(usize, char),
+    ) -> usize {
+        let mut index = 0;
+        let mut missing = 0;
+        content.for_each_char_infallible(|ch| {
+            if ch != '\n' && !self.font.has_glyph(ch) {
+                callback(index, ch);
+                missing += 1;
+            }
+            index += 1;
+        });
+        missing
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,176 @@
+use embedded_graphics_core::prelude::Point;
+
+use crate::{
+    content::LineDimensionsIterator,
+    font_reader::FontReader,
+    types::{HorizontalAlignment, PositionedGlyph},
+    utils::{HorizontalRenderedDimensions, Transform},
+    Content, LookupError,
+};
+
+use super::render_actions::{compute_glyph_dimensions, compute_horizontal_offset};
+
+/// An iterator over the positions of the characters of a text.
+///
+/// Created by [`FontRenderer::layout()`](crate::FontRenderer::layout).
+pub struct Layout<'a, C: Content> {
+    font: FontReader<'a>,
+    chars: C::CharIter,
+    line_dimensions: C::LineDimensionsIter,
+    horizontal_align: HorizontalAlignment,
+    transform: Transform,
+    position: Point,
+    advance: Point,
+    previous_char: Option<char>,
+    index: usize,
+    line_started: bool,
+    failed: bool,
+}
+
+impl<'a, C: Content> Layout<'a, C> {
+    pub(crate) fn new(
+        font: FontReader<'a>,
+        content: &C,
+        position: Point,
+        transform: Transform,
+        horizontal_align: HorizontalAlignment,
+    ) -> Self {
+        Self {
+            font,
+            chars: content.char_iterator(),
+            line_dimensions: content.line_dimensions_iterator(),
+            horizontal_align,
+            transform,
+            position,
+            advance: Point::new(0, 0),
+            previous_char: None,
+            index: 0,
+            line_started: false,
+            failed: false,
+        }
+    }
+
+    /// Computes the horizontal offset of the next line, like `render_aligned()` does.
+    fn start_line(&mut self) -> Result<(), LookupError> {
+        // Left alignment doesn't need to measure the line
+        let line = match self.horizontal_align {
+            HorizontalAlignment::Left => HorizontalRenderedDimensions::empty(),
+            HorizontalAlignment::Center | HorizontalAlignment::Right => {
+                self.line_dimensions.next(&self.font)?
+            }
+        };
+        self.advance.x = compute_horizontal_offset(self.horizontal_align, line);
+        self.previous_char = None;
+        self.line_started = true;
+        Ok(())
+    }
+
+    /// Lays out the next character, in the unscaled and unrotated text space.
+    fn next_unscaled(&mut self) -> Option<Result<PositionedGlyph, LookupError>> {
+        if self.failed {
+            return None;
+        }
+
+        let ch = self.chars.next()?;
+        let glyph = self.layout_char(ch);
+        self.failed = glyph.is_err();
+        Some(glyph)
+    }
+
+    fn layout_char(&mut self, ch: char) -> Result<PositionedGlyph, LookupError> {
+        if !self.line_started {
+            self.start_line()?;
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        if ch == '\n' {
+            let origin = self.position + self.advance;
+            self.advance.y += i32::try_from(self.font.line_height).unwrap();
+            self.line_started = false;
+            return Ok(PositionedGlyph {
+                index,
+                ch,
+                origin,
+                advance: Point::new(0, 0),
+                bounding_box: None,
+            });
+        }
+
+        self.advance.x += self.font.kerning(self.previous_char.replace(ch), ch);
+        let origin = self.position + self.advance;
+        let dimensions = compute_glyph_dimensions(ch, origin, &self.font)?;
+        self.advance += dimensions.advance;
+
+        Ok(PositionedGlyph {
+            index,
+            ch,
+            origin,
+            advance: dimensions.advance,
+            bounding_box: dimensions.bounding_box,
+        })
+    }
+
+    /// The position after the last character, in the unscaled and unrotated text space.
+    fn end_position(&mut self) -> Result<Point, LookupError> {
+        // An empty last line is never started by a character
+        if !self.line_started {
+            self.start_line()?;
+        }
+        Ok(self.position + self.advance)
+    }
+
+    /// The index of the character at the given pixel of the display.
+    ///
+    /// Every character occupies the area between its origin and its advance,
+    /// over the full height of its line.
+    pub(crate) fn char_index_at(mut self, point: Point) -> Result<Option<usize>, LookupError> {
+        let point = self.transform.inverse_pixel(point);
+
+        let line_height = i32::try_from(self.font.line_height).unwrap();
+        let line_top = -(i32::from(self.font.font_bounding_box_height)
+            + i32::from(self.font.font_bounding_box_y_offset));
+
+        while let Some(glyph) = self.next_unscaled() {
+            let glyph = glyph?;
+            let top = glyph.origin.y + line_top;
+            if (top..top + line_height).contains(&point.y)
+                && (glyph.origin.x..glyph.origin.x + glyph.advance.x).contains(&point.x)
+            {
+                return Ok(Some(glyph.index));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// The position on the baseline in front of the character with the given index.
+    ///
+    /// Indices past the end of the content refer to the position after the last character.
+    pub(crate) fn caret_position(mut self, index: usize) -> Result<Point, LookupError> {
+        while let Some(glyph) = self.next_unscaled() {
+            let glyph = glyph?;
+            if glyph.index == index {
+                return Ok(self.transform.position(glyph.origin));
+            }
+        }
+
+        let end = self.end_position()?;
+        Ok(self.transform.position(end))
+    }
+}
+
+impl<C: Content> Iterator for Layout<'_, C> {
+    type Item = Result<PositionedGlyph, LookupError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let glyph = self.next_unscaled()?;
+        Some(glyph.map(|glyph| PositionedGlyph {
+            origin: self.transform.position(glyph.origin),
+            advance: self.transform.vector(glyph.advance),
+            bounding_box: glyph.bounding_box.map(|b| self.transform.rect(b)),
+            ..glyph
+        }))
+    }
+}
@@ -0,0 +1,194 @@
+use embedded_graphics_core::{
+    prelude::{DrawTarget, Point},
+    primitives::Rectangle,
+};
+
+use crate::{
+    content::LineDimensionsIterator,
+    font_reader::{FontReader, GlyphReader},
+    types::{FontColor, HorizontalAlignment, VerticalPosition},
+    utils::{combine_bounding_boxes, fill_solid_clipped},
+    Content, Error, PrepareError,
+};
+
+use super::render_actions::{
+    compute_glyph_data_dimensions, compute_horizontal_offset, layers, line_background,
+    render_glyph_data, split_emulated_background,
+};
+
+/// Text that got laid out once, to be drawn many times.
+///
+/// Created by [`FontRenderer::prepare()`](crate::FontRenderer::prepare).
+///
+/// Stores the glyph data and the position of every glyph of the text,
+/// so drawing it again neither searches the font nor lays out the text.
+/// It can be drawn to any position and in any [`FontColor`].
+///
+/// `N` is the maximum number of glyphs the text can consist of.
+/// Newlines and [ignored](crate::FontRenderer::with_ignore_unknown_chars) characters don't count.
+#[derive(Debug, Clone)]
+pub struct PreparedText<'a, const N: usize> {
+    font: FontReader<'a>,
+    glyphs: [Option<PreparedGlyph<'a>>; N],
+    len: usize,
+    bounding_box: Option<Rectangle>,
+}
+
+#[derive(Debug, Clone)]
+struct PreparedGlyph<'a> {
+    glyph: GlyphReader<'a>,
+    /// The origin of the glyph, relative to the position the text gets drawn to
+    origin: Point,
+    advance: i32,
+    /// The emulated background of the line that this glyph starts
+    line_background: Option<Rectangle>,
+}
+
+impl<'a, const N: usize> PreparedText<'a, N> {
+    pub(crate) fn new(
+        font: &FontReader<'a>,
+        content: impl Content,
+        vertical_pos: VerticalPosition,
+        horizontal_align: HorizontalAlignment,
+    ) -> Result<Self, PrepareError> {
+        let mut this = Self {
+            font: font.clone(),
+            glyphs: core::array::from_fn(|_| None),
+            len: 0,
+            bounding_box: None,
+        };
+
+        let position = Point::new(0, content.compute_vertical_offset(font, vertical_pos));
+
+        // Mirrors `render_aligned()`, with the line dimensions always being
+        // computed for the emulated background.
+        let mut line_dimensions = content.line_dimensions_iterator();
+        let mut start_line = |advance_y: i32| {
+            let line = line_dimensions.next(font)?;
+            let advance = Point::new(
+                compute_horizontal_offset(horizontal_align, line.clone()),
+                advance_y,
+            );
+            Ok::<_, PrepareError>((advance, line_background(position + advance, &line, font)))
+        };
+
+        let (mut advance, mut line_background) = start_line(0)?;
+
+        let mut previous_char = None;
+
+        content.for_each_char(|ch| -> Result<(), PrepareError> {
+            if ch == '\n' {
+                (advance, line_background) =
+                    start_line(advance.y + i32::try_from(font.line_height).unwrap())?;
+                previous_char = None;
+                return Ok(());
+            }
+
+            advance.x += font.kerning(previous_char.replace(ch), ch);
+            let Some(glyph) = font.try_retrieve_glyph_data(ch)? else {
+                return Ok(());
+            };
+
+            let origin = position + advance;
+            let glyph_advance = glyph.advance() + font.spacing(ch);
+            let dimensions = compute_glyph_data_dimensions(&glyph, glyph_advance, origin, font);
+            this.bounding_box = combine_bounding_boxes(this.bounding_box, dimensions.bounding_box);
+
+            let slot = this
+                .glyphs
+                .get_mut(this.len)
+                .ok_or(PrepareError::TooManyGlyphs)?;
+            *slot = Some(PreparedGlyph {
+                glyph,
+                origin,
+                advance: dimensions.advance.x,
+                line_background: line_background.take(),
+            });
+            this.len += 1;
+
+            advance += dimensions.advance;
+            Ok(())
+        })?;
+
+        Ok(this)
+    }
+
+    /// The bounding box of the text, if drawn to `position`.
+    ///
+    /// As the color is unknown, it doesn't include the outline or the shadow of
+    /// [`FontColor::WithOutline`] and [`FontColor::WithShadow`];
+    /// [`FontRenderer::get_effect_bounding_box()`](crate::FontRenderer::get_effect_bounding_box) adds them.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to draw to.
+    ///
+    /// # Return
+    ///
+    /// The bounding box of the text.
+    ///
+    pub fn bounding_box(&self, position: Point) -> Option<Rectangle> {
+        let transform = self.font.transform(position);
+        self.bounding_box
+            .map(|b| transform.rect(Rectangle::new(b.top_left + position, b.size)))
+    }
+
+    /// Draws the text to a display.
+    ///
+    /// Produces exactly the same pixels as the rendering function the text was prepared for.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to draw to.
+    /// * `color` - The font color.
+    /// * `display` - The display to draw to.
+    ///
+    /// # Return
+    ///
+    /// The bounding box of the drawn text.
+    ///
+    pub fn draw<Display>(
+        &self,
+        position: Point,
+        color: FontColor<Display::Color>,
+        display: &mut Display,
+    ) -> Result<Option<Rectangle>, Error<Display::Error>>
+    where
+        Display: DrawTarget,
+    {
+        let font = &self.font;
+        if color.has_background() && !font.supports_background() {
+            return Err(Error::BackgroundColorNotSupported);
+        }
+
+        let transform = font.transform(position);
+
+        let mut bounding_box = None;
+
+        let (color, background) = split_emulated_background(color, font);
+
+        for &layer in layers(color) {
+            for prepared in self.glyphs.iter().take(self.len).flatten() {
+                if let (Some(background), Some(area)) = (background, prepared.line_background) {
+                    let area = Rectangle::new(area.top_left + position, area.size);
+                    fill_solid_clipped(display, &transform.rect(area), background)
+                        .map_err(Error::DisplayError)?;
+                }
+
+                let dimensions = render_glyph_data(
+                    &prepared.glyph,
+                    prepared.advance,
+                    position + prepared.origin,
+                    color,
+                    layer,
+                    font,
+                    &transform,
+                    display,
+                )?;
+                bounding_box = combine_bounding_boxes(bounding_box, dimensions.bounding_box);
+            }
+        }
+
+        Ok(bounding_box.map(|b| transform.rect(b)))
+    }
+}
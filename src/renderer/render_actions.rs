@@ -1,10 +1,20 @@
-use embedded_graphics_core::prelude::{DrawTarget, Point};
+use core::cmp;
+
+use embedded_graphics_core::{
+    pixelcolor::PixelColor,
+    prelude::{DrawTarget, Point, Size},
+    primitives::Rectangle,
+};
 
 use crate::{
-    font_reader::FontReader,
-    types::{FontColor, HorizontalAlignment, RenderedDimensions},
-    utils::HorizontalRenderedDimensions,
-    Error, LookupError,
+    error::DrawError,
+    font_reader::{FontReader, GlyphReader},
+    types::{FontColor, HorizontalAlignment, RenderedDimensions, VerticalPosition},
+    utils::{
+        combine_bounding_boxes, fill_solid_clipped, is_visible, HorizontalRenderedDimensions,
+        Transform,
+    },
+    LookupError,
 };
 
 pub fn compute_horizontal_offset(
@@ -33,6 +43,130 @@ pub fn compute_horizontal_offset(
     }
 }
 
+/// Computes the position to render wrapped text to, so that it ends up
+/// inside of `text_box` with the given alignment.
+///
+/// As a rectangle has no baseline, [`VerticalPosition::Baseline`] is treated
+/// like [`VerticalPosition::Top`].
+pub fn compute_wrapped_text_anchor(
+    text_box: Rectangle,
+    vertical_pos: VerticalPosition,
+    horizontal_align: HorizontalAlignment,
+) -> (Point, VerticalPosition) {
+    let width = i32::try_from(text_box.size.width).unwrap_or(i32::MAX);
+    let height = i32::try_from(text_box.size.height).unwrap_or(i32::MAX);
+    let Point { x: left, y: top } = text_box.top_left;
+
+    let x = match horizontal_align {
+        HorizontalAlignment::Left => left,
+        HorizontalAlignment::Center => left + width / 2,
+        HorizontalAlignment::Right => left + width - 1,
+    };
+
+    // `Top` and `Bottom` render the text right next to the anchor,
+    // so the anchor has to be just outside of the box.
+    match vertical_pos {
+        VerticalPosition::Baseline | VerticalPosition::Top => {
+            (Point::new(x, top - 1), VerticalPosition::Top)
+        }
+        VerticalPosition::Center => (Point::new(x, top + height / 2), VerticalPosition::Center),
+        VerticalPosition::Bottom => (Point::new(x, top + height), VerticalPosition::Bottom),
+    }
+}
+
+/// A horizontal decoration line, `offset` rows below the baseline of text at `position`
+/// that spans `advance` pixels.
+pub fn decoration_line(position: Point, offset: i32, advance: i32) -> Option<Rectangle> {
+    let width = u32::try_from(advance).ok().filter(|&w| w > 0)?;
+    Some(Rectangle::new(
+        Point::new(position.x, position.y + offset),
+        Size::new(width, 1),
+    ))
+}
+
+/// The decoration lines of the font along a glyph at `position`.
+fn glyph_decoration_lines(
+    position: Point,
+    advance: i32,
+    font: &FontReader,
+) -> impl Iterator<Item = Rectangle> {
+    let decorations = font.decorations;
+    let underline = decorations
+        .underline
+        .then(|| decoration_line(position, font.underline_offset(), advance));
+    let strikethrough = decorations
+        .strikethrough
+        .then(|| decoration_line(position, font.strikethrough_offset(), advance));
+
+    underline
+        .flatten()
+        .into_iter()
+        .chain(strikethrough.flatten())
+}
+
+/// Splits off the background color if the font emulates it.
+///
+/// The emulated background gets filled line by line, so the glyphs
+/// themselves get rendered transparently on top of it.
+pub fn split_emulated_background<C: PixelColor>(
+    color: FontColor<C>,
+    font: &FontReader,
+) -> (FontColor<C>, Option<C>) {
+    match color {
+        FontColor::WithBackground { fg, bg } if font.emulate_background => {
+            (FontColor::Transparent(fg), Some(bg))
+        }
+        color => (color, None),
+    }
+}
+
+/// The box behind a line of text at `position`.
+///
+/// Horizontally, it spans the advance and the bounding box of the line;
+/// vertically, it spans the bounding box of the font.
+pub fn line_background(
+    position: Point,
+    line: &HorizontalRenderedDimensions,
+    font: &FontReader,
+) -> Option<Rectangle> {
+    let left = if line.bounding_box_width > 0 {
+        cmp::min(line.bounding_box_offset, 0)
+    } else {
+        0
+    };
+    let width = u32::try_from(line.width() - left).ok().filter(|&w| w > 0)?;
+    let height = u32::try_from(font.font_bounding_box_height).ok()?;
+
+    Some(Rectangle::new(
+        Point::new(
+            position.x + left,
+            position.y
+                - (i32::from(font.font_bounding_box_height)
+                    + i32::from(font.font_bounding_box_y_offset)),
+        ),
+        Size::new(width, height),
+    ))
+}
+
+/// Fills the box behind a line of text at `position` with the background color, if any.
+pub fn fill_line_background<Display>(
+    position: Point,
+    line: &HorizontalRenderedDimensions,
+    background: Option<Display::Color>,
+    font: &FontReader,
+    transform: &Transform,
+    display: &mut Display,
+) -> Result<(), DrawError<Display::Error>>
+where
+    Display: DrawTarget,
+{
+    if let (Some(color), Some(area)) = (background, line_background(position, line, font)) {
+        fill_solid_clipped(display, &transform.rect(area), color)
+            .map_err(DrawError::DisplayError)?;
+    }
+    Ok(())
+}
+
 pub fn compute_glyph_dimensions(
     ch: char,
     position: Point,
@@ -45,20 +179,36 @@ pub fn compute_glyph_dimensions(
         }
     };
 
-    let advance = glyph.advance();
+    let advance = glyph.advance() + font.spacing(ch);
+    Ok(compute_glyph_data_dimensions(
+        &glyph, advance, position, font,
+    ))
+}
+
+/// Computes the dimensions of an already retrieved glyph, see [`compute_glyph_dimensions()`].
+pub fn compute_glyph_data_dimensions(
+    glyph: &GlyphReader,
+    advance: i32,
+    position: Point,
+    font: &FontReader,
+) -> RenderedDimensions {
     let size = glyph.size();
 
-    let bounding_box = if size.width > 0 && size.height > 0 {
+    let mut bounding_box = if size.width > 0 && size.height > 0 {
         let renderer = glyph.create_renderer();
         Some(renderer.get_glyph_bounding_box(position))
     } else {
         None
     };
 
-    Ok(RenderedDimensions {
-        advance: Point::new(advance.into(), 0),
+    for line in glyph_decoration_lines(position, advance, font) {
+        bounding_box = combine_bounding_boxes(bounding_box, Some(line));
+    }
+
+    RenderedDimensions {
+        advance: Point::new(advance, 0),
         bounding_box,
-    })
+    }
 }
 
 pub fn compute_horizontal_glyph_dimensions(
@@ -73,15 +223,27 @@ pub fn compute_horizontal_glyph_dimensions(
         }
     };
 
-    let advance = glyph.advance().into();
-    let width = glyph.width().into();
-    let left = glyph.left(position_x);
-
-    Ok(HorizontalRenderedDimensions {
+    let advance = glyph.advance() + font.spacing(ch);
+    let mut dimensions = HorizontalRenderedDimensions {
         advance,
-        bounding_box_offset: left,
-        bounding_box_width: width,
-    })
+        bounding_box_offset: glyph.left(position_x),
+        bounding_box_width: glyph.width(),
+    };
+
+    for line in glyph_decoration_lines(Point::new(position_x, 0), advance, font) {
+        let right = line.top_left.x + line.size.width as i32;
+        if dimensions.bounding_box_width > 0 {
+            let glyph_right = dimensions.bounding_box_offset + dimensions.bounding_box_width as i32;
+            dimensions.bounding_box_offset = dimensions.bounding_box_offset.min(line.top_left.x);
+            dimensions.bounding_box_width =
+                (right.max(glyph_right) - dimensions.bounding_box_offset) as u32;
+        } else {
+            dimensions.bounding_box_offset = line.top_left.x;
+            dimensions.bounding_box_width = line.size.width;
+        }
+    }
+
+    Ok(dimensions)
 }
 
 pub fn compute_horizontal_line_dimensions(
@@ -95,7 +257,9 @@ pub fn compute_horizontal_line_dimensions(
         bounding_box_offset: 0,
     };
 
+    let mut previous_char = None;
     for ch in line.chars() {
+        line_dimensions.advance += font.kerning(previous_char.replace(ch), ch);
         let dimensions =
             compute_horizontal_glyph_dimensions(ch, position_x + line_dimensions.advance, font)?;
         line_dimensions.add(dimensions);
@@ -105,13 +269,95 @@ pub fn compute_horizontal_line_dimensions(
     Ok(line_dimensions)
 }
 
+/// A part of the rendered text.
+///
+/// The layers get rendered in separate passes over the text, so that
+/// the outline or shadow of a glyph never covers the neighbouring glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// The outline or shadow of the glyphs.
+    Effect,
+    /// The glyphs themselves, including their background.
+    Foreground,
+}
+
+const OUTLINE_OFFSETS: [Point; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+const SHADOW_OFFSETS: [Point; 1] = [Point::new(1, 1)];
+
+/// The color of the effect, and the offsets of the glyph copies that form it.
+fn effect<C: PixelColor>(color: FontColor<C>) -> Option<(C, &'static [Point])> {
+    match color {
+        FontColor::WithOutline { outline, .. } => Some((outline, &OUTLINE_OFFSETS)),
+        FontColor::WithShadow { shadow, .. } => Some((shadow, &SHADOW_OFFSETS)),
+        FontColor::Transparent(_) | FontColor::WithBackground { .. } => None,
+    }
+}
+
+/// Whether the color draws an outline or a shadow.
+pub fn has_effect<C: PixelColor>(color: FontColor<C>) -> bool {
+    effect(color).is_some()
+}
+
+/// The layers that have to be rendered for the given color, in order.
+pub fn layers<C: PixelColor>(color: FontColor<C>) -> &'static [Layer] {
+    if has_effect(color) {
+        &[Layer::Effect, Layer::Foreground]
+    } else {
+        &[Layer::Foreground]
+    }
+}
+
+/// The area covered by copies of `rect`, shifted by each of the `offsets`.
+fn shifted_bounding_box(rect: Rectangle, offsets: &[Point]) -> Option<Rectangle> {
+    offsets.iter().fold(None, |bounding_box, &offset| {
+        combine_bounding_boxes(
+            bounding_box,
+            Some(Rectangle::new(rect.top_left + offset, rect.size)),
+        )
+    })
+}
+
+/// Grows a bounding box on the display by the outline or shadow of the color.
+///
+/// The effect offsets are given in text space, so they get rotated and scaled
+/// like the glyphs they belong to.
+pub fn grow_by_effect<C: PixelColor>(
+    bounding_box: Rectangle,
+    color: FontColor<C>,
+    transform: &Transform,
+) -> Option<Rectangle> {
+    let offsets = effect(color).map_or(&[][..], |(_, offsets)| offsets);
+    let shifted = offsets.iter().fold(None, |shifted, &offset| {
+        combine_bounding_boxes(
+            shifted,
+            Some(Rectangle::new(
+                bounding_box.top_left + transform.vector(offset),
+                bounding_box.size,
+            )),
+        )
+    });
+    combine_bounding_boxes(Some(bounding_box), shifted)
+}
+
 pub fn render_glyph<Display>(
     ch: char,
     position: Point,
     color: FontColor<Display::Color>,
+    layer: Layer,
     font: &FontReader,
+    transform: &Transform,
     display: &mut Display,
-) -> Result<RenderedDimensions, Error<Display::Error>>
+) -> Result<RenderedDimensions, DrawError<Display::Error>>
 where
     Display: DrawTarget,
 {
@@ -122,25 +368,93 @@ where
         }
     };
 
-    let advance = glyph.advance();
+    let advance = glyph.advance() + font.spacing(ch);
+    render_glyph_data(
+        &glyph, advance, position, color, layer, font, transform, display,
+    )
+}
+
+/// Renders an already retrieved glyph, see [`render_glyph()`].
+#[allow(clippy::too_many_arguments)]
+pub fn render_glyph_data<Display>(
+    glyph: &GlyphReader,
+    advance: i32,
+    position: Point,
+    color: FontColor<Display::Color>,
+    layer: Layer,
+    font: &FontReader,
+    transform: &Transform,
+    display: &mut Display,
+) -> Result<RenderedDimensions, DrawError<Display::Error>>
+where
+    Display: DrawTarget,
+{
     let size = glyph.size();
 
-    let bounding_box = if size.width > 0 && size.height > 0 {
-        let renderer = glyph.create_renderer();
-        Some(match color {
-            FontColor::Transparent(color) => {
-                renderer.render_transparent(position, display, color)?
+    // Glyphs outside of the display don't need to get decoded at all
+    let clip = display.bounding_box();
+
+    if layer == Layer::Effect {
+        let Some((effect_color, offsets)) = effect(color) else {
+            return Ok(RenderedDimensions {
+                advance: Point::new(advance, 0),
+                bounding_box: None,
+            });
+        };
+
+        let mut bounding_box = None;
+        if size.width > 0 && size.height > 0 {
+            let renderer = glyph.create_renderer();
+            let effect_box =
+                shifted_bounding_box(renderer.get_glyph_bounding_box(position), offsets);
+            if effect_box.is_some_and(|b| is_visible(&transform.rect(b), &clip)) {
+                renderer.render_shifted(position, transform, display, effect_color, offsets)?;
             }
-            FontColor::WithBackground { fg, bg } => {
-                renderer.render_as_box_fill(position, display, fg, bg)?
+            bounding_box = effect_box;
+        }
+
+        for line in glyph_decoration_lines(position, advance, font) {
+            for &offset in offsets {
+                let shifted = Rectangle::new(line.top_left + offset, line.size);
+                fill_solid_clipped(display, &transform.rect(shifted), effect_color)
+                    .map_err(DrawError::DisplayError)?;
             }
-        })
+            bounding_box =
+                combine_bounding_boxes(bounding_box, shifted_bounding_box(line, offsets));
+        }
+
+        return Ok(RenderedDimensions {
+            advance: Point::new(advance, 0),
+            bounding_box,
+        });
+    }
+
+    let mut bounding_box = if size.width > 0 && size.height > 0 {
+        let renderer = glyph.create_renderer();
+        let glyph_box = renderer.get_glyph_bounding_box(position);
+        if is_visible(&transform.rect(glyph_box), &clip) {
+            match color {
+                FontColor::WithBackground { fg, bg } => {
+                    renderer.render_as_box_fill(position, transform, display, fg, bg)?
+                }
+                color => {
+                    renderer.render_transparent(position, transform, display, color.foreground())?
+                }
+            };
+        }
+        Some(glyph_box)
     } else {
         None
     };
 
+    for line in glyph_decoration_lines(position, advance, font) {
+        fill_solid_clipped(display, &transform.rect(line), color.foreground())
+            .map_err(DrawError::DisplayError)?;
+        bounding_box = combine_bounding_boxes(bounding_box, Some(line));
+    }
+
     Ok(RenderedDimensions {
-        advance: Point::new(advance.into(), 0),
+        advance: Point::new(advance, 0),
         bounding_box,
     })
 }
@@ -148,6 +462,8 @@ where
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
+    use embedded_graphics_core::prelude::Size;
+
     use super::*;
 
     #[test]
@@ -201,4 +517,50 @@ mod tests {
 
         assert_eq!(offset, -4);
     }
+
+    #[test]
+    fn WrappedTextAnchor_Corners() {
+        let text_box = Rectangle::new(Point::new(10, 20), Size::new(30, 40));
+
+        assert_eq!(
+            compute_wrapped_text_anchor(text_box, VerticalPosition::Top, HorizontalAlignment::Left),
+            (Point::new(10, 19), VerticalPosition::Top)
+        );
+        assert_eq!(
+            compute_wrapped_text_anchor(
+                text_box,
+                VerticalPosition::Bottom,
+                HorizontalAlignment::Right
+            ),
+            (Point::new(39, 60), VerticalPosition::Bottom)
+        );
+    }
+
+    #[test]
+    fn WrappedTextAnchor_Center() {
+        let text_box = Rectangle::new(Point::new(10, 20), Size::new(30, 40));
+
+        assert_eq!(
+            compute_wrapped_text_anchor(
+                text_box,
+                VerticalPosition::Center,
+                HorizontalAlignment::Center
+            ),
+            (Point::new(25, 40), VerticalPosition::Center)
+        );
+    }
+
+    #[test]
+    fn WrappedTextAnchor_Baseline_IsTop() {
+        let text_box = Rectangle::new(Point::new(10, 20), Size::new(30, 40));
+
+        assert_eq!(
+            compute_wrapped_text_anchor(
+                text_box,
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left
+            ),
+            compute_wrapped_text_anchor(text_box, VerticalPosition::Top, HorizontalAlignment::Left)
+        );
+    }
 }
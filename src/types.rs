@@ -14,9 +14,10 @@ use embedded_graphics_core::{
 /// are relative to [`VerticalPosition::Baseline`].
 ///
 /// The default is [`VerticalPosition::Baseline`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum VerticalPosition {
     /// Anchored at the font baseline
+    #[default]
     Baseline,
     /// Anchored at the top
     Top,
@@ -26,12 +27,6 @@ pub enum VerticalPosition {
     Bottom,
 }
 
-impl Default for VerticalPosition {
-    fn default() -> Self {
-        Self::Baseline
-    }
-}
-
 /// The dimensions of a rendered glyph/text.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RenderedDimensions {
@@ -55,6 +50,33 @@ impl RenderedDimensions {
     }
 }
 
+/// The dimensions of word-wrapped text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedWrappedDimensions {
+    /// The bounding box of the rendered text.
+    ///
+    /// Can be `None` if nothing was rendered, like for
+    /// a text consisting only of whitespace.
+    pub bounding_box: Option<Rectangle>,
+    /// The number of lines the text got wrapped into,
+    /// including lines that were produced by newline characters.
+    pub line_count: u32,
+}
+
+/// The dimensions of text that got truncated to a maximum width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedTruncatedDimensions {
+    /// The advance in x and y direction, including the ellipsis.
+    pub advance: Point,
+    /// The bounding box of the rendered text, including the ellipsis.
+    ///
+    /// Can be `None` if nothing was rendered, like for
+    /// a text consisting only of whitespace.
+    pub bounding_box: Option<Rectangle>,
+    /// Whether the text had to be shortened.
+    pub truncated: bool,
+}
+
 /// The horizontal rendering position of the font.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HorizontalAlignment {
@@ -66,9 +88,244 @@ pub enum HorizontalAlignment {
     Right,
 }
 
+/// The horizontal positioning of word-wrapped text inside of its rectangle.
+///
+/// See [`FontRenderer::render_wrapped()`](crate::FontRenderer::render_wrapped).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrappedAlignment {
+    /// Aligned to the left side
+    Left,
+    /// Centered
+    Center,
+    /// Aligned to the right side
+    Right,
+    /// Stretches the gaps between words so that both sides are flush.
+    ///
+    /// The last line of every paragraph stays left aligned.
+    Justify,
+}
+
+impl From<HorizontalAlignment> for WrappedAlignment {
+    fn from(horizontal_align: HorizontalAlignment) -> Self {
+        match horizontal_align {
+            HorizontalAlignment::Left => Self::Left,
+            HorizontalAlignment::Center => Self::Center,
+            HorizontalAlignment::Right => Self::Right,
+        }
+    }
+}
+
+/// The rotation of the rendered text.
+///
+/// Equivalent to the font direction of U8g2 (`setFontDirection`).
+/// The text gets rotated around the position it is rendered to.
+///
+/// The default is [`FontRotation::Rotate0`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontRotation {
+    /// Not rotated, renders from left to right
+    #[default]
+    Rotate0,
+    /// Rotated by 90 degrees clockwise, renders from top to bottom
+    Rotate90,
+    /// Rotated by 180 degrees, renders from right to left
+    Rotate180,
+    /// Rotated by 270 degrees clockwise, renders from bottom to top
+    Rotate270,
+}
+
+/// Which glyphs get rendered in cells of a fixed width, like in a monospace font.
+///
+/// See [`FontRenderer::with_fixed_width()`](crate::FontRenderer::with_fixed_width).
+///
+/// The default is [`FixedWidth::Proportional`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixedWidth {
+    /// Every glyph keeps its own advance
+    #[default]
+    Proportional,
+    /// Only the digits `0` to `9` get rendered in cells, also known as tabular figures
+    Digits(CellWidth),
+    /// Every glyph gets rendered in a cell
+    AllGlyphs(CellWidth),
+}
+
+/// The width of the cells of [`FixedWidth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellWidth {
+    /// The largest advance of the digits `0` to `9`.
+    ///
+    /// It gets looked up once at the start of every rendering or measuring call.
+    /// Fonts without digits render proportionally.
+    WidestDigit,
+    /// A fixed width, in font pixels
+    Pixels(u8),
+}
+
+/// The spacing adjustment between two specific characters.
+///
+/// See [`FontRenderer::with_kerning()`](crate::FontRenderer::with_kerning).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KerningPair {
+    /// The left character of the pair
+    pub first: char,
+    /// The right character of the pair
+    pub second: char,
+    /// The change of the distance between the two characters, in pixels.
+    ///
+    /// Negative values move the characters closer together.
+    pub adjustment: i8,
+}
+
+impl KerningPair {
+    /// Creates a kerning pair.
+    pub const fn new(first: char, second: char, adjustment: i8) -> Self {
+        Self {
+            first,
+            second,
+            adjustment,
+        }
+    }
+}
+
+/// Lines that get drawn along the rendered text.
+///
+/// Every line spans the advance of the text and gets drawn in the font color.
+///
+/// See [`FontRenderer::with_decorations()`](crate::FontRenderer::with_decorations).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextDecorations {
+    /// A line below the baseline, derived from the descent of the font.
+    pub underline: bool,
+    /// A line through the middle of the characters, at half of the ascent of the font.
+    pub strikethrough: bool,
+}
+
+impl TextDecorations {
+    /// No decorations.
+    pub const NONE: Self = Self {
+        underline: false,
+        strikethrough: false,
+    };
+
+    /// Enables or disables the underline.
+    pub const fn with_underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    /// Enables or disables the strikethrough.
+    pub const fn with_strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+}
+
+/// The header of a font, as stored in the font data.
+///
+/// Returned by [`FontRenderer::get_font_info()`](crate::FontRenderer::get_font_info).
+/// All values are unscaled and unrotated; for the metrics of a configured renderer,
+/// use [`get_scaled_ascent()`](crate::FontRenderer::get_scaled_ascent) and its siblings instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontInfo {
+    /// The number of glyphs in the font, truncated to 8 bits.
+    pub glyph_count: u8,
+    /// Whether the glyphs of the font can be rendered with a background color.
+    pub supports_background_color: bool,
+    /// The number of bits of a run length of background pixels.
+    pub bits_per_0: u8,
+    /// The number of bits of a run length of foreground pixels.
+    pub bits_per_1: u8,
+    /// The number of bits of the width of a glyph.
+    pub bits_per_char_width: u8,
+    /// The number of bits of the height of a glyph.
+    pub bits_per_char_height: u8,
+    /// The number of bits of the horizontal offset of a glyph.
+    pub bits_per_char_x: u8,
+    /// The number of bits of the vertical offset of a glyph.
+    pub bits_per_char_y: u8,
+    /// The number of bits of the advance of a glyph.
+    pub bits_per_delta_x: u8,
+    /// The width of the bounding box of all glyphs.
+    pub bounding_box_width: i8,
+    /// The height of the bounding box of all glyphs.
+    pub bounding_box_height: i8,
+    /// The horizontal offset of the bounding box of all glyphs.
+    pub bounding_box_x_offset: i8,
+    /// The vertical offset of the bounding box of all glyphs, relative to the baseline.
+    pub bounding_box_y_offset: i8,
+    /// The height of the character `A` above the baseline.
+    pub ascent: i8,
+    /// The depth of the character `g` below the baseline; usually negative.
+    pub descent: i8,
+    /// The height of the character `(` above the baseline.
+    pub ascent_of_parentheses: i8,
+    /// The depth of the character `(` below the baseline; usually negative.
+    pub descent_of_parentheses: i8,
+    /// The offset of the glyph of `A` in the glyph table.
+    pub start_pos_upper_a: u16,
+    /// The offset of the glyph of `a` in the glyph table.
+    pub start_pos_lower_a: u16,
+    /// The offset of the unicode section in the glyph table.
+    pub start_pos_unicode: u16,
+}
+
+/// The metrics of a single glyph.
+///
+/// Returned by [`FontRenderer::glyph_metrics()`](crate::FontRenderer::glyph_metrics).
+/// All values are scaled, but unrotated; they include synthetic styles and the
+/// configured letter and word spacing, just like the layout of the renderer.
+///
+/// A glyph rendered to `origin` covers the area from
+/// `(origin.x + offset_x, origin.y - offset_y - height)` to
+/// `(origin.x + offset_x + width, origin.y - offset_y)`, exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlyphMetrics {
+    /// The width of the glyph.
+    pub width: u32,
+    /// The height of the glyph.
+    pub height: u32,
+    /// The horizontal offset of the left edge of the glyph from its origin.
+    pub offset_x: i32,
+    /// The vertical offset of the bottom edge of the glyph above the baseline.
+    pub offset_y: i32,
+    /// The horizontal distance from the origin of the glyph to the origin of the next glyph,
+    /// not including kerning.
+    pub advance: i32,
+}
+
+/// A character of a text, at the position it gets rendered to.
+///
+/// Produced by [`FontRenderer::layout()`](crate::FontRenderer::layout).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionedGlyph {
+    /// The index of the character in the content.
+    pub index: usize,
+    /// The character.
+    pub ch: char,
+    /// The position on the baseline the glyph gets rendered at.
+    ///
+    /// Rendering the character on its own to this position
+    /// with [`VerticalPosition::Baseline`] produces the same glyph.
+    /// For a newline character, this is the end of its line.
+    pub origin: Point,
+    /// The relative offset to the origin of the next character in the same line.
+    ///
+    /// Zero for newline characters.
+    pub advance: Point,
+    /// The bounding box of the rendered glyph.
+    ///
+    /// `None` if nothing gets rendered, like for whitespace and newline characters.
+    pub bounding_box: Option<Rectangle>,
+}
+
 /// The color of the rendered text.
+///
+/// More ways of coloring text may be added in the future,
+/// so matching on it requires a wildcard arm.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum FontColor<Color>
 where
     Color: PixelColor,
@@ -84,6 +341,25 @@ where
         /// The background color
         bg: Color,
     },
+    /// Draw the text with a one pixel wide outline around every glyph.
+    ///
+    /// Helps to keep text readable on top of busy backgrounds.
+    /// The outline grows the bounding box by one pixel on every side.
+    WithOutline {
+        /// The foreground color
+        fg: Color,
+        /// The outline color
+        outline: Color,
+    },
+    /// Draw the text with a shadow, one pixel below and to the right of every glyph.
+    ///
+    /// The shadow grows the bounding box by one pixel to the right and to the bottom.
+    WithShadow {
+        /// The foreground color
+        fg: Color,
+        /// The shadow color
+        shadow: Color,
+    },
 }
 
 impl<Color> FontColor<Color>
@@ -93,6 +369,15 @@ where
     pub(crate) fn has_background(&self) -> bool {
         matches!(self, Self::WithBackground { .. })
     }
+
+    pub(crate) fn foreground(&self) -> Color {
+        match *self {
+            Self::Transparent(fg)
+            | Self::WithBackground { fg, .. }
+            | Self::WithOutline { fg, .. }
+            | Self::WithShadow { fg, .. } => fg,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -118,11 +403,76 @@ mod tests {
         implements_traits(RenderedDimensions::empty());
     }
 
+    #[test]
+    fn fixedwidth_implements_traits() {
+        implements_traits(FixedWidth::Digits(CellWidth::WidestDigit));
+    }
+
+    #[test]
+    fn kerningpair_implements_traits() {
+        implements_traits(KerningPair::new('A', 'V', -1));
+    }
+
+    #[test]
+    fn textdecorations_implements_traits() {
+        implements_traits(TextDecorations::NONE.with_underline(true));
+    }
+
+    #[test]
+    fn renderedwrappeddimensions_implements_traits() {
+        implements_traits(RenderedWrappedDimensions {
+            bounding_box: None,
+            line_count: 1,
+        });
+    }
+
+    #[test]
+    fn renderedtruncateddimensions_implements_traits() {
+        implements_traits(RenderedTruncatedDimensions {
+            advance: Point::new(0, 0),
+            bounding_box: None,
+            truncated: false,
+        });
+    }
+
+    #[test]
+    fn fontinfo_implements_traits() {
+        implements_traits(
+            crate::FontRenderer::new::<crate::fonts::u8g2_font_helvR08_tr>().get_font_info(),
+        );
+    }
+
+    #[test]
+    fn glyphmetrics_implements_traits() {
+        implements_traits(GlyphMetrics::default());
+    }
+
+    #[test]
+    fn positionedglyph_implements_traits() {
+        implements_traits(PositionedGlyph {
+            index: 0,
+            ch: 'a',
+            origin: Point::new(0, 0),
+            advance: Point::new(0, 0),
+            bounding_box: None,
+        });
+    }
+
     #[test]
     fn horizontalalignment_implements_traits() {
         implements_traits(HorizontalAlignment::Center);
     }
 
+    #[test]
+    fn wrappedalignment_implements_traits() {
+        implements_traits(WrappedAlignment::from(HorizontalAlignment::Center));
+    }
+
+    #[test]
+    fn fontrotation_implements_traits() {
+        implements_traits(FontRotation::Rotate90);
+    }
+
     #[test]
     fn fontcolor_implements_traits() {
         implements_traits(FontColor::Transparent(Rgb888::BLACK));
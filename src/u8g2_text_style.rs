@@ -1,6 +1,6 @@
 use embedded_graphics::text::{
     renderer::{CharacterStyle, TextMetrics, TextRenderer},
-    Baseline,
+    Baseline, DecorationColor,
 };
 
 use embedded_graphics_core::{
@@ -10,6 +10,7 @@ use embedded_graphics_core::{
 
 use crate::{
     types::{FontColor, VerticalPosition},
+    utils::fill_solid_clipped,
     Error, Font, FontRenderer,
 };
 
@@ -34,8 +35,12 @@ pub struct U8g2TextStyle<C> {
     pub text_color: Option<C>,
     /// Background color.
     pub background_color: Option<C>,
+    /// Underline color.
+    pub underline_color: DecorationColor<C>,
+    /// Strikethrough color.
+    pub strikethrough_color: DecorationColor<C>,
     /// The font renderer
-    font: FontRenderer,
+    font: FontRenderer<'static>,
 }
 
 impl<C> U8g2TextStyle<C> {
@@ -46,11 +51,51 @@ impl<C> U8g2TextStyle<C> {
         Self {
             text_color: Some(text_color),
             background_color: None,
+            underline_color: DecorationColor::None,
+            strikethrough_color: DecorationColor::None,
             font: FontRenderer::new::<F>().with_ignore_unknown_chars(true),
         }
     }
 }
 
+impl<C> U8g2TextStyle<C>
+where
+    C: PixelColor,
+{
+    /// Draws the underline and the strikethrough of a line of text
+    /// that spans `advance` pixels, if their colors are set.
+    fn draw_decorations<D>(
+        &self,
+        advance: i32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let (underline, strikethrough) =
+            self.font
+                .decoration_lines(position, baseline.into(), advance);
+
+        for (line, color) in [
+            (underline, self.underline_color),
+            (strikethrough, self.strikethrough_color),
+        ] {
+            let color = match color {
+                DecorationColor::None => None,
+                DecorationColor::TextColor => self.text_color,
+                DecorationColor::Custom(color) => Some(color),
+            };
+            if let (Some(line), Some(color)) = (line, color) {
+                fill_solid_clipped(target, &line, color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<C> TextRenderer for U8g2TextStyle<C>
 where
     C: PixelColor,
@@ -93,9 +138,10 @@ where
                 .map_err(Into::into);
         };
 
-        result
-            .map_err(unwrap_error)
-            .map(|dims| position + dims.advance)
+        let advance = result.map_err(unwrap_error)?.advance;
+        self.draw_decorations(advance.x, adjusted_position, baseline, target)?;
+
+        Ok(position + advance)
     }
 
     fn draw_whitespace<D>(
@@ -118,10 +164,22 @@ where
                     let top_left = bounding_box.top_left;
                     let height = bounding_box.size.height;
 
-                    target
-                        .fill_solid(&Rectangle::new(top_left, Size::new(width, height)), color)?;
+                    fill_solid_clipped(
+                        target,
+                        &Rectangle::new(top_left, Size::new(width, height)),
+                        color,
+                    )?;
                 }
             }
+
+            // For some reason, font baseline in embedded-graphics seems to be shifted by one
+            let mut adjusted_position = position;
+            if let Baseline::Alphabetic = baseline {
+                adjusted_position.y += 1;
+            }
+
+            let advance = i32::try_from(width).unwrap_or(i32::MAX);
+            self.draw_decorations(advance, adjusted_position, baseline, target)?;
         }
 
         Ok(position + Size::new(width, 0))
@@ -145,13 +203,13 @@ where
     }
 
     fn line_height(&self) -> u32 {
-        self.font.get_default_line_height()
+        self.font.get_scaled_default_line_height()
     }
 }
 
-/// Note that `U8g2TextStyle` does not support underline or strikethrough,
-/// so [`set_underline_color()`](CharacterStyle::set_underline_color) and
-/// [`set_strikethrough_color()`](CharacterStyle::set_strikethrough_color()) does nothing.
+/// The underline and the strikethrough span the advance of the text.
+/// Their positions are derived from the descent and the ascent of the font,
+/// like the [decorations](crate::types::TextDecorations) of [`FontRenderer`].
 impl<C> CharacterStyle for U8g2TextStyle<C>
 where
     C: PixelColor,
@@ -165,6 +223,14 @@ where
     fn set_background_color(&mut self, background_color: Option<Self::Color>) {
         self.background_color = background_color;
     }
+
+    fn set_underline_color(&mut self, underline_color: DecorationColor<Self::Color>) {
+        self.underline_color = underline_color;
+    }
+
+    fn set_strikethrough_color(&mut self, strikethrough_color: DecorationColor<Self::Color>) {
+        self.strikethrough_color = strikethrough_color;
+    }
 }
 
 impl<C> TextRenderer for &U8g2TextStyle<C>
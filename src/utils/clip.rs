@@ -0,0 +1,52 @@
+use embedded_graphics_core::{prelude::DrawTarget, primitives::Rectangle};
+
+/// Fills the part of `area` that is visible on the display.
+///
+/// `DrawTarget::fill_solid()` is not required to clip, so without this,
+/// areas far outside of the display would still produce every single pixel.
+pub fn fill_solid_clipped<Display>(
+    display: &mut Display,
+    area: &Rectangle,
+    color: Display::Color,
+) -> Result<(), Display::Error>
+where
+    Display: DrawTarget,
+{
+    let visible = area.intersection(&display.bounding_box());
+    if visible.is_zero_sized() {
+        return Ok(());
+    }
+    display.fill_solid(&visible, color)
+}
+
+/// Whether any part of `area` is visible inside of `clip`.
+pub fn is_visible(area: &Rectangle, clip: &Rectangle) -> bool {
+    !area.intersection(clip).is_zero_sized()
+}
+
+/// Whether `area` is completely visible inside of `clip`.
+pub fn is_fully_visible(area: &Rectangle, clip: &Rectangle) -> bool {
+    match area.bottom_right() {
+        Some(bottom_right) => clip.contains(area.top_left) && clip.contains(bottom_right),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::prelude::{Point, Size};
+
+    use super::*;
+
+    #[test]
+    fn checks_visibility() {
+        let clip = Rectangle::new(Point::new(10, 10), Size::new(10, 10));
+        let inside = Rectangle::new(Point::new(12, 12), Size::new(3, 3));
+        let partial = Rectangle::new(Point::new(18, 5), Size::new(5, 10));
+        let outside = Rectangle::new(Point::new(20, 10), Size::new(5, 5));
+
+        assert!(is_visible(&inside, &clip) && is_fully_visible(&inside, &clip));
+        assert!(is_visible(&partial, &clip) && !is_fully_visible(&partial, &clip));
+        assert!(!is_visible(&outside, &clip) && !is_fully_visible(&outside, &clip));
+    }
+}
@@ -44,6 +44,34 @@ impl HorizontalRenderedDimensions {
             self.bounding_box_width = (right - left) as u32;
         }
     }
+
+    /// Appends a glyph that got measured at position `0` to the end of a line.
+    ///
+    /// Unlike [`add()`](Self::add), glyphs without a bounding box
+    /// (like spaces) don't influence the bounding box of the line.
+    pub fn append_glyph(&mut self, glyph: &HorizontalRenderedDimensions) {
+        if glyph.bounding_box_width > 0 {
+            self.add(HorizontalRenderedDimensions {
+                advance: glyph.advance,
+                bounding_box_width: glyph.bounding_box_width,
+                bounding_box_offset: glyph.bounding_box_offset + self.advance,
+            });
+        } else {
+            self.advance += glyph.advance;
+        }
+    }
+
+    /// The horizontal space a line occupies, measured from the line start.
+    pub fn width(&self) -> i32 {
+        if self.bounding_box_width > 0 {
+            cmp::max(
+                self.advance,
+                self.bounding_box_offset + self.bounding_box_width as i32,
+            )
+        } else {
+            self.advance
+        }
+    }
 }
 
 #[cfg(test)]
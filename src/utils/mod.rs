@@ -1,9 +1,13 @@
+mod clip;
 mod combine_bounding_boxes;
 mod debug_ignore;
 mod format_args_reader;
 mod horizontal_rendered_dimensions;
+mod transform;
 
+pub use clip::{fill_solid_clipped, is_fully_visible, is_visible};
 pub use combine_bounding_boxes::combine_bounding_boxes;
 pub use debug_ignore::DebugIgnore;
 pub use format_args_reader::{FormatArgsReader, FormatArgsReaderInfallible};
 pub use horizontal_rendered_dimensions::HorizontalRenderedDimensions;
+pub use transform::Transform;
@@ -0,0 +1,320 @@
+use embedded_graphics_core::{
+    prelude::{Point, Size},
+    primitives::Rectangle,
+};
+
+use crate::types::{FontRotation, RenderedDimensions};
+
+/// Maps the unscaled and unrotated layout of a text onto the display.
+///
+/// All layout computations happen as if the text was rendered without scaling and rotation;
+/// the transform then scales and rotates the result around `origin`, which is the position
+/// the text was rendered to.
+///
+/// Coordinates are treated as the top left corner of a pixel, so rotating
+/// a pixel around the origin results in another pixel and not in a shifted one.
+#[derive(Debug, Clone)]
+pub struct Transform {
+    origin: Point,
+    rotation: FontRotation,
+    scale: u32,
+}
+
+impl Transform {
+    pub const fn new(origin: Point, rotation: FontRotation, scale: u32) -> Self {
+        Self {
+            origin,
+            rotation,
+            scale,
+        }
+    }
+
+    pub const fn is_identity(&self) -> bool {
+        matches!(self.rotation, FontRotation::Rotate0) && self.scale == 1
+    }
+
+    pub const fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Transforms a relative vector, like an advance.
+    pub const fn vector(&self, vector: Point) -> Point {
+        let scale = self.scale as i32;
+        let (x, y) = (vector.x * scale, vector.y * scale);
+        match self.rotation {
+            FontRotation::Rotate0 => Point::new(x, y),
+            FontRotation::Rotate90 => Point::new(-y, x),
+            FontRotation::Rotate180 => Point::new(-x, -y),
+            FontRotation::Rotate270 => Point::new(y, -x),
+        }
+    }
+
+    /// Transforms a position, like the origin of a glyph.
+    ///
+    /// Unlike a pixel, a position is the corner between pixels, so it rotates like a vector.
+    pub const fn position(&self, position: Point) -> Point {
+        let offset = self.vector(Point::new(
+            position.x - self.origin.x,
+            position.y - self.origin.y,
+        ));
+        Point::new(self.origin.x + offset.x, self.origin.y + offset.y)
+    }
+
+    /// Transforms the position of a single pixel.
+    ///
+    /// Only meaningful without scaling, as a scaled pixel is a rectangle.
+    pub const fn pixel(&self, pixel: Point) -> Point {
+        self.rect(Rectangle::new(pixel, Size::new(1, 1))).top_left
+    }
+
+    /// Transforms a rectangle.
+    pub const fn rect(&self, rect: Rectangle) -> Rectangle {
+        let scale = self.scale as i32;
+        let x = (rect.top_left.x - self.origin.x) * scale;
+        let y = (rect.top_left.y - self.origin.y) * scale;
+        let width = rect.size.width * self.scale;
+        let height = rect.size.height * self.scale;
+
+        let (x, y, size) = match self.rotation {
+            FontRotation::Rotate0 => (x, y, Size::new(width, height)),
+            FontRotation::Rotate90 => (-y - height as i32, x, Size::new(height, width)),
+            FontRotation::Rotate180 => (
+                -x - width as i32,
+                -y - height as i32,
+                Size::new(width, height),
+            ),
+            FontRotation::Rotate270 => (y, -x - width as i32, Size::new(height, width)),
+        };
+
+        Rectangle::new(Point::new(self.origin.x + x, self.origin.y + y), size)
+    }
+
+    /// The inverse of [`rect()`](Self::rect).
+    ///
+    /// Maps a rectangle on the display back to the unscaled and unrotated layout.
+    /// If the rectangle doesn't consist of whole scaled pixels, it gets shrunk until it does.
+    pub fn inverse_rect(&self, rect: Rectangle) -> Rectangle {
+        let corner_a = self.inverse_rotate(rect.top_left - self.origin);
+        let corner_b = self.inverse_rotate(rect.top_left + rect.size - self.origin);
+
+        let scale = self.scale as i32;
+        let div_floor = |value: i32| value.div_euclid(scale);
+        let div_ceil = |value: i32| -(-value).div_euclid(scale);
+
+        let left = div_ceil(corner_a.x.min(corner_b.x));
+        let top = div_ceil(corner_a.y.min(corner_b.y));
+        let right = div_floor(corner_a.x.max(corner_b.x));
+        let bottom = div_floor(corner_a.y.max(corner_b.y));
+
+        Rectangle::new(
+            self.origin + Point::new(left, top),
+            Size::new(
+                right.saturating_sub(left).max(0) as u32,
+                bottom.saturating_sub(top).max(0) as u32,
+            ),
+        )
+    }
+
+    /// Maps a pixel on the display back to the unscaled and unrotated pixel that covers it.
+    pub fn inverse_pixel(&self, pixel: Point) -> Point {
+        let corner_a = self.inverse_rotate(pixel - self.origin);
+        let corner_b = self.inverse_rotate(pixel + Point::new(1, 1) - self.origin);
+
+        let scale = self.scale as i32;
+        self.origin
+            + Point::new(
+                corner_a.x.min(corner_b.x).div_euclid(scale),
+                corner_a.y.min(corner_b.y).div_euclid(scale),
+            )
+    }
+
+    const fn inverse_rotate(&self, vector: Point) -> Point {
+        let (x, y) = (vector.x, vector.y);
+        match self.rotation {
+            FontRotation::Rotate0 => Point::new(x, y),
+            FontRotation::Rotate90 => Point::new(y, -x),
+            FontRotation::Rotate180 => Point::new(-x, -y),
+            FontRotation::Rotate270 => Point::new(-y, x),
+        }
+    }
+
+    pub fn dimensions(&self, dimensions: RenderedDimensions) -> RenderedDimensions {
+        RenderedDimensions {
+            advance: self.vector(dimensions.advance),
+            bounding_box: dimensions.bounding_box.map(|b| self.rect(b)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use embedded_graphics_core::primitives::PointsIter;
+
+    use super::*;
+
+    const ORIGIN: Point = Point::new(10, 20);
+
+    fn transform(rotation: FontRotation) -> Transform {
+        Transform::new(ORIGIN, rotation, 1)
+    }
+
+    #[test]
+    fn Rect_Rotate0_IsUnchanged() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        assert_eq!(transform(FontRotation::Rotate0).rect(rect), rect);
+    }
+
+    #[test]
+    fn Rect_Rotate90_RotatesClockwise() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        assert_eq!(
+            transform(FontRotation::Rotate90).rect(rect),
+            Rectangle::new(Point::new(10, 22), Size::new(5, 3))
+        );
+    }
+
+    #[test]
+    fn Rect_Rotate180_FlipsAroundOrigin() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        assert_eq!(
+            transform(FontRotation::Rotate180).rect(rect),
+            Rectangle::new(Point::new(5, 20), Size::new(3, 5))
+        );
+    }
+
+    #[test]
+    fn Rect_Rotate270_RotatesCounterClockwise() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        assert_eq!(
+            transform(FontRotation::Rotate270).rect(rect),
+            Rectangle::new(Point::new(5, 15), Size::new(5, 3))
+        );
+    }
+
+    #[test]
+    fn Pixel_RotatesAroundOriginCorner() {
+        let pixel = ORIGIN;
+        assert_eq!(transform(FontRotation::Rotate0).pixel(pixel), ORIGIN);
+        assert_eq!(
+            transform(FontRotation::Rotate90).pixel(pixel),
+            Point::new(9, 20)
+        );
+        assert_eq!(
+            transform(FontRotation::Rotate180).pixel(pixel),
+            Point::new(9, 19)
+        );
+        assert_eq!(
+            transform(FontRotation::Rotate270).pixel(pixel),
+            Point::new(10, 19)
+        );
+    }
+
+    #[test]
+    fn Rect_Scaled_ScalesAroundOrigin() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        assert_eq!(
+            Transform::new(ORIGIN, FontRotation::Rotate0, 3).rect(rect),
+            Rectangle::new(Point::new(16, 5), Size::new(9, 15))
+        );
+        assert_eq!(
+            Transform::new(ORIGIN, FontRotation::Rotate90, 2).rect(rect),
+            Rectangle::new(Point::new(10, 24), Size::new(10, 6))
+        );
+    }
+
+    #[test]
+    fn InverseRect_UndoesRect() {
+        let rect = Rectangle::new(Point::new(12, 15), Size::new(3, 5));
+        for rotation in [
+            FontRotation::Rotate0,
+            FontRotation::Rotate90,
+            FontRotation::Rotate180,
+            FontRotation::Rotate270,
+        ] {
+            for scale in [1, 2, 3] {
+                let transform = Transform::new(ORIGIN, rotation, scale);
+                assert_eq!(transform.inverse_rect(transform.rect(rect)), rect);
+            }
+        }
+    }
+
+    #[test]
+    fn InverseRect_ShrinksToWholePixels() {
+        let transform = Transform::new(ORIGIN, FontRotation::Rotate0, 2);
+        assert_eq!(
+            transform.inverse_rect(Rectangle::new(Point::new(11, 20), Size::new(6, 5))),
+            Rectangle::new(Point::new(11, 20), Size::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn InversePixel_FindsCoveringPixel() {
+        let pixel = Point::new(12, 15);
+        for rotation in [
+            FontRotation::Rotate0,
+            FontRotation::Rotate90,
+            FontRotation::Rotate180,
+            FontRotation::Rotate270,
+        ] {
+            for scale in [1, 2, 3] {
+                let transform = Transform::new(ORIGIN, rotation, scale);
+                let scaled = transform.rect(Rectangle::new(pixel, Size::new(1, 1)));
+                for p in scaled.points() {
+                    assert_eq!(transform.inverse_pixel(p), pixel);
+                }
+                assert_ne!(
+                    transform.inverse_pixel(scaled.top_left - Point::new(1, 1)),
+                    pixel
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn IsIdentity_OnlyWithoutRotationAndScale() {
+        assert!(Transform::new(ORIGIN, FontRotation::Rotate0, 1).is_identity());
+        assert!(!Transform::new(ORIGIN, FontRotation::Rotate0, 2).is_identity());
+        assert!(!Transform::new(ORIGIN, FontRotation::Rotate180, 1).is_identity());
+    }
+
+    #[test]
+    fn Vector_Scales() {
+        assert_eq!(
+            Transform::new(ORIGIN, FontRotation::Rotate270, 2).vector(Point::new(5, 1)),
+            Point::new(2, -10)
+        );
+    }
+
+    #[test]
+    fn Vector_Rotates() {
+        let v = Point::new(5, 1);
+        assert_eq!(transform(FontRotation::Rotate0).vector(v), Point::new(5, 1));
+        assert_eq!(
+            transform(FontRotation::Rotate90).vector(v),
+            Point::new(-1, 5)
+        );
+        assert_eq!(
+            transform(FontRotation::Rotate180).vector(v),
+            Point::new(-5, -1)
+        );
+        assert_eq!(
+            transform(FontRotation::Rotate270).vector(v),
+            Point::new(1, -5)
+        );
+    }
+
+    #[test]
+    fn Position_RotatesAroundOrigin() {
+        let p = ORIGIN + Point::new(5, 1);
+        assert_eq!(transform(FontRotation::Rotate0).position(p), p);
+        assert_eq!(
+            transform(FontRotation::Rotate90).position(p),
+            ORIGIN + Point::new(-1, 5)
+        );
+        assert_eq!(
+            Transform::new(ORIGIN, FontRotation::Rotate180, 2).position(p),
+            ORIGIN + Point::new(-10, -2)
+        );
+    }
+}
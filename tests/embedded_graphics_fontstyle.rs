@@ -7,9 +7,11 @@ mod util;
 mod textstyle_tests {
     use super::*;
     use embedded_graphics::{
+        image::Image,
+        primitives::PointsIter,
         text::{
             renderer::{CharacterStyle, TextRenderer},
-            Alignment, Baseline, Text, TextStyleBuilder,
+            Alignment, Baseline, DecorationColor, Text, TextStyleBuilder,
         },
         Drawable,
     };
@@ -22,8 +24,8 @@ mod textstyle_tests {
 
     use u8g2_fonts::{
         fonts,
-        types::{HorizontalAlignment, VerticalPosition},
-        Error, U8g2TextStyle,
+        types::{FontColor, HorizontalAlignment, TextDecorations, VerticalPosition},
+        Error, FontRenderer, U8g2TextStyle,
     };
 
     use util::{FailingDrawTarget, TestDrawTarget};
@@ -118,6 +120,151 @@ mod textstyle_tests {
         );
     }
 
+    #[test]
+    fn render_text_with_decorations() {
+        let positions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_decorations_embedded_graphics.png"),
+            |display| {
+                let mut character_style =
+                    U8g2TextStyle::new(fonts::u8g2_font_10x20_mf, Rgb888::new(237, 28, 36));
+                character_style.set_background_color(Some(Rgb888::new(1, 1, 1)));
+                character_style.set_underline_color(DecorationColor::TextColor);
+                character_style
+                    .set_strikethrough_color(DecorationColor::Custom(Rgb888::new(0, 162, 232)));
+
+                let first = Text::new("Hello, W0rld!", Point::new(2, 19), character_style)
+                    .draw(display)
+                    .unwrap();
+
+                let mut character_style =
+                    U8g2TextStyle::new(fonts::u8g2_font_helvR08_tr, Rgb888::new(237, 28, 36));
+                character_style
+                    .set_underline_color(DecorationColor::Custom(Rgb888::new(0, 162, 232)));
+
+                let second = Text::new("Jumpy fox,\nquick!", Point::new(2, 32), character_style)
+                    .draw(display)
+                    .unwrap();
+
+                (first, second)
+            },
+        );
+
+        assert_eq!(positions, (Point::new(132, 19), Point::new(28, 44)));
+    }
+
+    #[test]
+    fn decorations_match_font_renderer() {
+        let color = Rgb888::new(237, 28, 36);
+        let text = "Jumpy fox, quick!";
+
+        for baseline in [
+            Baseline::Alphabetic,
+            Baseline::Top,
+            Baseline::Middle,
+            Baseline::Bottom,
+        ] {
+            let mut character_style = U8g2TextStyle::new(fonts::u8g2_font_helvR08_tr, color);
+            character_style.set_underline_color(DecorationColor::TextColor);
+            character_style.set_strikethrough_color(DecorationColor::TextColor);
+
+            let mut expected = TestDrawTarget::new(Size::new(100, 60));
+            let mut position = Point::new(3, 30);
+            if let Baseline::Alphabetic = baseline {
+                position.y += 1;
+            }
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_decorations(
+                    TextDecorations::NONE
+                        .with_underline(true)
+                        .with_strikethrough(true),
+                )
+                .render(
+                    text,
+                    position,
+                    baseline.into(),
+                    FontColor::Transparent(color),
+                    &mut expected,
+                )
+                .unwrap();
+
+            let mut actual = TestDrawTarget::new(Size::new(100, 60));
+            character_style
+                .draw_string(text, Point::new(3, 30), baseline, &mut actual)
+                .unwrap();
+
+            for p in Rectangle::new(Point::zero(), Size::new(100, 60)).points() {
+                assert_eq!(actual.get_pixel(p), expected.get_pixel(p), "{:?}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn render_whitespace_with_decorations() {
+        let foreground_color = Rgb888::new(237, 28, 36);
+        let mut character_style = U8g2TextStyle::new(fonts::u8g2_font_10x20_mf, foreground_color);
+        character_style.set_underline_color(DecorationColor::TextColor);
+
+        let mut display = TestDrawTarget::new(Size::new(30, 30));
+        let pos = character_style
+            .draw_whitespace(5, Point::new(3, 5), Baseline::Top, &mut display)
+            .unwrap();
+        assert_eq!(pos, Point::new(8, 5));
+
+        let drawn = Rectangle::new(Point::zero(), Size::new(30, 30))
+            .points()
+            .filter(|&p| display.get_pixel(p) == Some(foreground_color))
+            .collect::<Vec<_>>();
+        assert_eq!(drawn.len(), 5);
+        assert!(drawn.iter().all(|p| p.y == drawn[0].y));
+    }
+
+    #[test]
+    fn clips_text_outside_of_display() {
+        let mut character_style =
+            U8g2TextStyle::new(fonts::u8g2_font_10x20_mf, Rgb888::new(237, 28, 36));
+        character_style.set_background_color(Some(Rgb888::new(63, 72, 204)));
+        character_style.set_underline_color(DecorationColor::TextColor);
+
+        let mut display = TestDrawTarget::new(Size::new(30, 30));
+        let text = Text::new("Hello,   W0rld!", Point::new(-40, 12), character_style);
+        let next = text.draw(&mut display).unwrap();
+
+        assert_eq!(next, Point::new(110, 12));
+        assert_eq!(display.out_of_bounds_pixels(), 0);
+    }
+
+    #[test]
+    fn draws_bitmaps_as_images() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        let color = Rgb888::new(237, 28, 36);
+        let position = Point::new(5, 20);
+
+        let mut expected = TestDrawTarget::new(Size::new(80, 40));
+        font.render(
+            "Agi,\nW0",
+            position,
+            VerticalPosition::Baseline,
+            FontColor::Transparent(color),
+            &mut expected,
+        )
+        .unwrap();
+
+        let mut buffer = [0; 400];
+        let bitmap = font
+            .render_to_bitmap("Agi,\nW0", VerticalPosition::Baseline, &mut buffer, 8)
+            .unwrap()
+            .with_color(color);
+
+        let mut display = TestDrawTarget::new(Size::new(80, 40));
+        Image::new(&bitmap, position + bitmap.offset())
+            .draw(&mut display)
+            .unwrap();
+
+        for p in display.bounding_box().points() {
+            assert_eq!(display.get_pixel(p), expected.get_pixel(p), "{:?}", p);
+        }
+    }
+
     #[test]
     fn render_text_without_text_color() {
         let dimensions =
@@ -1,20 +1,170 @@
 mod util;
 
 use embedded_graphics_core::{
+    image::ImageDrawable,
     pixelcolor::Rgb888,
     prelude::{Dimensions, DrawTarget, OriginDimensions, Point, Size, WebColors},
-    primitives::Rectangle,
+    primitives::{PointsIter, Rectangle},
 };
 use u8g2_fonts::{
     fonts,
-    types::{FontColor, HorizontalAlignment, RenderedDimensions, VerticalPosition},
-    Error, FontRenderer,
+    types::{
+        CellWidth, FixedWidth, FontColor, FontRotation, GlyphMetrics, HorizontalAlignment,
+        KerningPair, RenderedDimensions, RenderedTruncatedDimensions, RenderedWrappedDimensions,
+        TextDecorations, VerticalPosition, WrappedAlignment,
+    },
+    Bitmap, BitmapError, Content, Error, FontDataError, FontRenderer, GlyphIndex, Layout,
+    LookupError, PrepareError,
 };
 
 use util::{FailingDrawTarget, TestDrawTarget};
 
 mod alignment_grid;
 
+static KERNING: [KerningPair; 4] = [
+    KerningPair::new('A', 'V', -3),
+    KerningPair::new('T', 'o', -3),
+    KerningPair::new('V', 'A', -3),
+    KerningPair::new('W', 'a', -2),
+];
+
+/// Checks that rendering and measuring `text` agree with each other, for all alignments
+/// and for both `text` itself and `format_args!()`, and that nothing gets drawn outside
+/// of the measured bounding box.
+fn assert_consistent(font: &FontRenderer, text: impl Content + Copy + core::fmt::Display) {
+    let position = Point::new(150, 150);
+    let color = FontColor::Transparent(Rgb888::CSS_RED);
+
+    let check_pixels = |display: &TestDrawTarget, bounding_box: Option<Rectangle>| {
+        for p in display.bounding_box().points() {
+            if display.get_pixel(p) != Some(Rgb888::CSS_WHITE) {
+                assert!(
+                    bounding_box.is_some_and(|b| b.contains(p)),
+                    "{:?} of {:?} is outside of {:?}",
+                    p,
+                    text.to_string(),
+                    bounding_box
+                );
+            }
+        }
+    };
+
+    let mut display = TestDrawTarget::new(Size::new(300, 300));
+    let rendered = font
+        .render(
+            text,
+            position,
+            VerticalPosition::Baseline,
+            color,
+            &mut display,
+        )
+        .unwrap();
+    check_pixels(&display, rendered.bounding_box);
+    let rendered_args = font
+        .render(
+            format_args!("{}", text),
+            position,
+            VerticalPosition::Baseline,
+            color,
+            &mut TestDrawTarget::new(Size::new(300, 300)),
+        )
+        .unwrap();
+    assert_eq!(rendered, rendered_args);
+    for computed in [
+        font.get_rendered_dimensions(text, position, VerticalPosition::Baseline),
+        font.get_rendered_dimensions(
+            format_args!("{}", text),
+            position,
+            VerticalPosition::Baseline,
+        ),
+    ] {
+        assert_eq!(rendered, computed.unwrap());
+    }
+
+    for horizontal_align in [
+        HorizontalAlignment::Left,
+        HorizontalAlignment::Center,
+        HorizontalAlignment::Right,
+    ] {
+        let mut display = TestDrawTarget::new(Size::new(300, 300));
+        let rendered = font
+            .render_aligned(
+                text,
+                position,
+                VerticalPosition::Center,
+                horizontal_align,
+                color,
+                &mut display,
+            )
+            .unwrap();
+        check_pixels(&display, rendered);
+        let rendered_args = font
+            .render_aligned(
+                format_args!("{}", text),
+                position,
+                VerticalPosition::Center,
+                horizontal_align,
+                color,
+                &mut TestDrawTarget::new(Size::new(300, 300)),
+            )
+            .unwrap();
+        assert_eq!(rendered, rendered_args);
+        for computed in [
+            font.get_rendered_dimensions_aligned(
+                text,
+                position,
+                VerticalPosition::Center,
+                horizontal_align,
+            ),
+            font.get_rendered_dimensions_aligned(
+                format_args!("{}", text),
+                position,
+                VerticalPosition::Center,
+                horizontal_align,
+            ),
+        ] {
+            assert_eq!(rendered, computed.unwrap());
+        }
+    }
+
+    let rect = Rectangle::new(Point::new(10, 10), Size::new(120, 280));
+    for horizontal_align in [
+        WrappedAlignment::Left,
+        WrappedAlignment::Center,
+        WrappedAlignment::Right,
+        WrappedAlignment::Justify,
+    ] {
+        let mut display = TestDrawTarget::new(Size::new(300, 300));
+        let rendered = font
+            .render_wrapped(
+                text,
+                rect,
+                VerticalPosition::Top,
+                horizontal_align,
+                color,
+                &mut display,
+            )
+            .unwrap();
+        check_pixels(&display, rendered.bounding_box);
+        for computed in [
+            font.get_rendered_dimensions_wrapped(
+                text,
+                rect,
+                VerticalPosition::Top,
+                horizontal_align,
+            ),
+            font.get_rendered_dimensions_wrapped(
+                format_args!("{}", text),
+                rect,
+                VerticalPosition::Top,
+                horizontal_align,
+            ),
+        ] {
+            assert_eq!(rendered, computed.unwrap());
+        }
+    }
+}
+
 #[test]
 fn letters_not_supported() {
     let mut display = TestDrawTarget::new(Size::new(1, 1));
@@ -1073,7 +1223,7 @@ fn whitespace_str_does_not_crash() {
     }
 }
 
-const ALPHABET_PYRAMID: &'static str = "a\nbb\nccc\ndddd\neeeee\nffffff\nggggggg\nhhhhhhhh\niiiiiiiii\njjjjjjjjjj\nkkkkkkkkkkk\nllllllllllll\nmmmmmmmmmmmmm\nnnnnnnnnnnnnnn\nooooooooooooooo\npppppppppppppppp\nqqqqqqqqqqqqqqqqq\nrrrrrrrrrrrrrrrrrr\nsssssssssssssssssss\ntttttttttttttttttttt\nuuuuuuuuuuuuuuuuuuuuu\nvvvvvvvvvvvvvvvvvvvvvv\nwwwwwwwwwwwwwwwwwwwwwww\nxxxxxxxxxxxxxxxxxxxxxxxx\nyyyyyyyyyyyyyyyyyyyyyyyyy\nzzzzzzzzzzzzzzzzzzzzzzzzzz";
+const ALPHABET_PYRAMID: &str = "a\nbb\nccc\ndddd\neeeee\nffffff\nggggggg\nhhhhhhhh\niiiiiiiii\njjjjjjjjjj\nkkkkkkkkkkk\nllllllllllll\nmmmmmmmmmmmmm\nnnnnnnnnnnnnnn\nooooooooooooooo\npppppppppppppppp\nqqqqqqqqqqqqqqqqq\nrrrrrrrrrrrrrrrrrr\nsssssssssssssssssss\ntttttttttttttttttttt\nuuuuuuuuuuuuuuuuuuuuu\nvvvvvvvvvvvvvvvvvvvvvv\nwwwwwwwwwwwwwwwwwwwwwww\nxxxxxxxxxxxxxxxxxxxxxxxx\nyyyyyyyyyyyyyyyyyyyyyyyyy\nzzzzzzzzzzzzzzzzzzzzzzzzzz";
 
 #[test]
 fn large_content_text() {
@@ -1164,6 +1314,7 @@ fn large_content_args() {
 }
 
 #[test]
+#[allow(clippy::single_element_loop)]
 fn whitespace_glyph_does_not_crash() {
     let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
 
@@ -1300,3 +1451,3207 @@ fn passes_on_error() {
         font.render_aligned(format_args!("{}", 1), position, vpos, hpos, color, display)
     });
 }
+
+mod rotation {
+    use super::*;
+
+    #[test]
+    fn render_text_rotated() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        let position = Point::new(50, 50);
+
+        TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_rotated.png"),
+            |display| {
+                display
+                    .fill_solid(
+                        &Rectangle::new(Point::new(50, 0), Size::new(1, 100)),
+                        Rgb888::new(63, 72, 204),
+                    )
+                    .unwrap();
+                display
+                    .fill_solid(
+                        &Rectangle::new(Point::new(0, 50), Size::new(100, 1)),
+                        Rgb888::new(63, 72, 204),
+                    )
+                    .unwrap();
+
+                for (rotation, color, expected) in [
+                    (
+                        FontRotation::Rotate0,
+                        Rgb888::new(237, 28, 36),
+                        RenderedDimensions {
+                            advance: Point::new(31, 0),
+                            bounding_box: Some(Rectangle::new(
+                                Point::new(49, 51),
+                                Size::new(31, 18),
+                            )),
+                        },
+                    ),
+                    (
+                        FontRotation::Rotate90,
+                        Rgb888::new(34, 177, 76),
+                        RenderedDimensions {
+                            advance: Point::new(0, 31),
+                            bounding_box: Some(Rectangle::new(
+                                Point::new(31, 49),
+                                Size::new(18, 31),
+                            )),
+                        },
+                    ),
+                    (
+                        FontRotation::Rotate180,
+                        Rgb888::new(255, 127, 39),
+                        RenderedDimensions {
+                            advance: Point::new(-31, 0),
+                            bounding_box: Some(Rectangle::new(
+                                Point::new(20, 31),
+                                Size::new(31, 18),
+                            )),
+                        },
+                    ),
+                    (
+                        FontRotation::Rotate270,
+                        Rgb888::new(163, 73, 164),
+                        RenderedDimensions {
+                            advance: Point::new(0, -31),
+                            bounding_box: Some(Rectangle::new(
+                                Point::new(51, 20),
+                                Size::new(18, 31),
+                            )),
+                        },
+                    ),
+                ] {
+                    let font = font.clone().with_rotation(rotation);
+
+                    let dimensions = font
+                        .render(
+                            "Agi",
+                            position,
+                            VerticalPosition::Top,
+                            FontColor::Transparent(color),
+                            display,
+                        )
+                        .unwrap();
+
+                    assert_eq!(dimensions, expected);
+                    assert_eq!(
+                        font.get_rendered_dimensions("Agi", position, VerticalPosition::Top)
+                            .unwrap(),
+                        expected
+                    );
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn render_text_rotated_with_background_color() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_rotated_background.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_10x20_mf>()
+                    .with_rotation(FontRotation::Rotate270)
+                    .render(
+                        "W0j",
+                        Point::new(5, 65),
+                        VerticalPosition::Top,
+                        FontColor::WithBackground {
+                            fg: Rgb888::new(237, 28, 36),
+                            bg: Rgb888::new(1, 1, 1),
+                        },
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(0, -30),
+                bounding_box: Some(Rectangle::new(Point::new(3, 35), Size::new(20, 30)))
+            }
+        );
+    }
+
+    #[test]
+    fn aligned_text_rotated() {
+        let text = "Agi,\niagmA!";
+        let position = Point::new(40, 30);
+
+        for rotation in [
+            FontRotation::Rotate0,
+            FontRotation::Rotate90,
+            FontRotation::Rotate180,
+            FontRotation::Rotate270,
+        ] {
+            let unrotated = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+            let font = unrotated.clone().with_rotation(rotation);
+
+            for hpos in [
+                HorizontalAlignment::Left,
+                HorizontalAlignment::Center,
+                HorizontalAlignment::Right,
+            ] {
+                let mut display = TestDrawTarget::new(Size::new(80, 60));
+
+                let dimensions = font
+                    .get_rendered_dimensions_aligned(text, position, VerticalPosition::Center, hpos)
+                    .unwrap();
+                let rendered_dimensions = font
+                    .render_aligned(
+                        text,
+                        position,
+                        VerticalPosition::Center,
+                        hpos,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        &mut display,
+                    )
+                    .unwrap();
+                assert_eq!(dimensions, rendered_dimensions);
+
+                let unrotated_dimensions = unrotated
+                    .get_rendered_dimensions_aligned(text, position, VerticalPosition::Center, hpos)
+                    .unwrap()
+                    .unwrap();
+                let dimensions = dimensions.unwrap();
+                match rotation {
+                    FontRotation::Rotate0 | FontRotation::Rotate180 => {
+                        assert_eq!(dimensions.size, unrotated_dimensions.size)
+                    }
+                    FontRotation::Rotate90 | FontRotation::Rotate270 => {
+                        assert_eq!(dimensions.size.width, unrotated_dimensions.size.height);
+                        assert_eq!(dimensions.size.height, unrotated_dimensions.size.width);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_font_bounding_box_rotated() {
+        let font = FontRenderer::new::<fonts::u8g2_font_osb21_tf>();
+
+        assert_eq!(
+            font.clone()
+                .with_rotation(FontRotation::Rotate90)
+                .get_font_bounding_box(VerticalPosition::Baseline),
+            Rectangle::new(Point::new(-8, -1), Size::new(36, 31))
+        );
+        assert_eq!(
+            font.clone()
+                .with_rotation(FontRotation::Rotate180)
+                .get_font_bounding_box(VerticalPosition::Baseline),
+            Rectangle::new(Point::new(-30, -8), Size::new(31, 36))
+        );
+        assert_eq!(
+            font.with_rotation(FontRotation::Rotate270)
+                .get_font_bounding_box(VerticalPosition::Baseline),
+            Rectangle::new(Point::new(-28, -30), Size::new(36, 31))
+        );
+    }
+}
+
+mod scaling {
+    use super::*;
+
+    #[test]
+    fn render_text_scaled() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_scaled.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_5x7_tr>()
+                    .with_scale(3)
+                    .render(
+                        "12:34",
+                        Point::new(5, 5),
+                        VerticalPosition::Top,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(75, 0),
+                bounding_box: Some(Rectangle::new(Point::new(8, 8), Size::new(69, 18)))
+            }
+        );
+    }
+
+    #[test]
+    fn render_text_scaled_and_rotated_with_background_color() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_scaled_background.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_5x7_mr>()
+                    .with_scale(2)
+                    .with_rotation(FontRotation::Rotate90)
+                    .render(
+                        "Hi\nyou",
+                        Point::new(55, 5),
+                        VerticalPosition::Top,
+                        FontColor::WithBackground {
+                            fg: Rgb888::new(237, 28, 36),
+                            bg: Rgb888::new(1, 1, 1),
+                        },
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(-16, 30),
+                bounding_box: Some(Rectangle::new(Point::new(23, 5), Size::new(30, 30)))
+            }
+        );
+    }
+
+    #[test]
+    fn scaled_text_matches_unscaled_text() {
+        let text = "Agi,\niagmA!";
+        let color = FontColor::Transparent(Rgb888::new(237, 28, 36));
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+
+        let unscaled_position = Point::new(50, 2);
+        let mut unscaled = TestDrawTarget::new(Size::new(100, 60));
+        let unscaled_box = font
+            .render_aligned(
+                text,
+                unscaled_position,
+                VerticalPosition::Top,
+                HorizontalAlignment::Center,
+                color,
+                &mut unscaled,
+            )
+            .unwrap()
+            .unwrap();
+
+        for scale in [2, 3] {
+            let font = font.clone().with_scale(scale);
+
+            let position = unscaled_position * scale as i32;
+            let mut scaled = TestDrawTarget::new(Size::new(100, 60) * scale);
+            let scaled_box = font
+                .render_aligned(
+                    text,
+                    position,
+                    VerticalPosition::Top,
+                    HorizontalAlignment::Center,
+                    color,
+                    &mut scaled,
+                )
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(
+                scaled_box,
+                font.get_rendered_dimensions_aligned(
+                    text,
+                    position,
+                    VerticalPosition::Top,
+                    HorizontalAlignment::Center,
+                )
+                .unwrap()
+                .unwrap()
+            );
+            assert_eq!(scaled_box.size, unscaled_box.size * scale);
+            assert_eq!(scaled_box.top_left, unscaled_box.top_left * scale as i32);
+
+            for y in 0..(60 * scale as i32) {
+                for x in 0..(100 * scale as i32) {
+                    let scaled_pixel = scaled.get_pixel(Point::new(x, y)).unwrap();
+                    let unscaled_pixel =
+                        unscaled.get_pixel(Point::new(x, y) / scale as i32).unwrap();
+                    assert_eq!(scaled_pixel, unscaled_pixel, "Pixel ({x}, {y}) differs");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_metrics_scaled() {
+        let font = FontRenderer::new::<fonts::u8g2_font_osb21_tf>().with_scale(2);
+
+        assert_eq!(font.get_ascent(), 21);
+        assert_eq!(font.get_descent(), -7);
+        assert_eq!(font.get_scaled_ascent(), 42);
+        assert_eq!(font.get_scaled_descent(), -14);
+        assert_eq!(font.get_default_line_height(), 37);
+        assert_eq!(font.get_scaled_default_line_height(), 74);
+        assert_eq!(
+            font.get_font_bounding_box(VerticalPosition::Baseline),
+            Rectangle::new(Point::new(-2, -56), Size::new(62, 72))
+        );
+    }
+
+    #[test]
+    fn default_line_height_can_be_set_again_when_scaled() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_scale(2);
+        let reset = font
+            .clone()
+            .with_line_height(font.get_default_line_height());
+
+        let render = |font: &FontRenderer| {
+            let mut display = TestDrawTarget::new(Size::new(200, 200));
+            let dimensions = font
+                .render(
+                    "Hello,\nWorld!",
+                    Point::new(2, 30),
+                    VerticalPosition::Baseline,
+                    FontColor::Transparent(Rgb888::CSS_RED),
+                    &mut display,
+                )
+                .unwrap();
+            (dimensions, display)
+        };
+
+        let (expected, expected_display) = render(&font);
+        let (dimensions, display) = render(&reset);
+        assert_eq!(dimensions, expected);
+        assert_eq!(
+            dimensions.advance.y,
+            i32::try_from(font.get_scaled_default_line_height()).unwrap()
+        );
+        for point in display.bounding_box().points() {
+            assert_eq!(display.get_pixel(point), expected_display.get_pixel(point));
+        }
+    }
+}
+
+mod font_data {
+    use super::*;
+
+    #[test]
+    fn render_text_from_runtime_data() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/fonts/u8g2_font_ncenB14_tr.u8g2font"
+        ))
+        .unwrap();
+
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text.png"),
+            |display| {
+                FontRenderer::from_data(&data)
+                    .render(
+                        "Hello World!",
+                        Point::new(2, 15),
+                        VerticalPosition::default(),
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(121, 0),
+                bounding_box: Some(Rectangle::new(Point::new(2, 1), Size::new(120, 14)))
+            }
+        );
+    }
+
+    #[test]
+    fn all_builtin_fonts_pass_validation() {
+        let fonts_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/fonts");
+
+        let mut font_count = 0;
+        for entry in std::fs::read_dir(fonts_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "u8g2font") {
+                let data = std::fs::read(&path).unwrap();
+                assert!(
+                    FontRenderer::try_from_data(&data).is_ok(),
+                    "{} failed validation",
+                    path.display()
+                );
+                font_count += 1;
+            }
+        }
+
+        assert!(font_count > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "too short to contain a font header")]
+    fn from_data_rejects_data_shorter_than_header() {
+        let _ = FontRenderer::from_data(&[0; 22]);
+    }
+
+    #[test]
+    fn try_from_data_rejects_invalid_header() {
+        assert!(matches!(
+            FontRenderer::try_from_data(&[0; 10]),
+            Err(FontDataError::InvalidHeader)
+        ));
+        assert!(matches!(
+            FontRenderer::try_from_data(&[0; 100]),
+            Err(FontDataError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn corrupt_font_data_never_panics() {
+        fn render_all(data: &[u8]) {
+            let Ok(font) = FontRenderer::try_from_data(data) else {
+                return;
+            };
+
+            let mut display = TestDrawTarget::new(Size::new(64, 32));
+            for color in [
+                FontColor::Transparent(Rgb888::CSS_RED),
+                FontColor::WithBackground {
+                    fg: Rgb888::CSS_RED,
+                    bg: Rgb888::CSS_BLUE,
+                },
+            ] {
+                let _ = font.render(
+                    "Ag!\nπλ\u{fffe}",
+                    Point::new(10, 20),
+                    VerticalPosition::default(),
+                    color,
+                    &mut display,
+                );
+                let _ = font.get_rendered_dimensions(
+                    "Ag!\nπλ\u{fffe}",
+                    Point::new(10, 20),
+                    VerticalPosition::Top,
+                );
+            }
+        }
+
+        for font_name in [
+            "u8g2_font_ncenB14_tr",
+            "u8g2_font_10x20_mf",
+            "u8g2_font_10x20_t_greek",
+        ] {
+            let data = std::fs::read(format!(
+                "{}/src/fonts/{}.u8g2font",
+                env!("CARGO_MANIFEST_DIR"),
+                font_name
+            ))
+            .unwrap();
+
+            for len in 0..data.len() {
+                render_all(&data[..len]);
+            }
+
+            for pos in 0..data.len() {
+                for mask in [0x01, 0x10, 0x80, 0xff] {
+                    let mut corrupted = data.clone();
+                    corrupted[pos] ^= mask;
+                    render_all(&corrupted);
+                }
+            }
+        }
+    }
+}
+
+mod wrapping {
+    use super::*;
+
+    #[test]
+    fn render_wrapped() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_wrapped.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .render_wrapped(
+                    "The quick brown fox jumps over the lazy dog.\nSupercalifragilisticexpialidocious!",
+                    Rectangle::new(Point::new(5, 5), Size::new(90, 70)),
+                    VerticalPosition::Center,
+                    WrappedAlignment::Center,
+                    FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                    display,
+                )
+                .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedWrappedDimensions {
+                bounding_box: Some(Rectangle::new(Point::new(7, 11), Size::new(86, 58))),
+                line_count: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn render_wrapped_rotated_with_background_color() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_wrapped_rotated.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_5x7_mr>()
+                    .with_rotation(FontRotation::Rotate90)
+                    .render_wrapped(
+                        "Wrapped and rotated text, with a background",
+                        Rectangle::new(Point::new(5, 5), Size::new(40, 100)),
+                        VerticalPosition::Top,
+                        WrappedAlignment::Left,
+                        FontColor::WithBackground {
+                            fg: Rgb888::new(237, 28, 36),
+                            bg: Rgb888::new(1, 1, 1),
+                        },
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedWrappedDimensions {
+                bounding_box: Some(Rectangle::new(Point::new(22, 5), Size::new(23, 95))),
+                line_count: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn render_wrapped_justified() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_wrapped_justified.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .render_wrapped(
+                    "The quick brown fox jumps over the lazy dog. It barked.\nA new paragraph starts here and ends.",
+                    Rectangle::new(Point::new(5, 5), Size::new(90, 70)),
+                    VerticalPosition::Top,
+                    WrappedAlignment::Justify,
+                    FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                    display,
+                )
+                .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedWrappedDimensions {
+                bounding_box: Some(Rectangle::new(Point::new(5, 5), Size::new(89, 68))),
+                line_count: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn wrapped_dimensions_match_rendered_dimensions() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit,\n\nsed do eiusmod tempor incididunt ut labore et dolore magna aliqua.";
+        let rect = Rectangle::new(Point::new(3, 4), Size::new(50, 220));
+
+        for vertical_pos in [
+            VerticalPosition::Baseline,
+            VerticalPosition::Top,
+            VerticalPosition::Center,
+            VerticalPosition::Bottom,
+        ] {
+            for horizontal_align in [
+                WrappedAlignment::Left,
+                WrappedAlignment::Center,
+                WrappedAlignment::Right,
+                WrappedAlignment::Justify,
+            ] {
+                let mut display = TestDrawTarget::new(Size::new(60, 230));
+                let rendered = font
+                    .render_wrapped(
+                        text,
+                        rect,
+                        vertical_pos,
+                        horizontal_align,
+                        FontColor::Transparent(Rgb888::CSS_RED),
+                        &mut display,
+                    )
+                    .unwrap();
+
+                let computed = font
+                    .get_rendered_dimensions_wrapped(text, rect, vertical_pos, horizontal_align)
+                    .unwrap();
+                let computed_args = font
+                    .get_rendered_dimensions_wrapped(
+                        format_args!("{}", text),
+                        rect,
+                        vertical_pos,
+                        horizontal_align,
+                    )
+                    .unwrap();
+
+                assert_eq!(rendered, computed);
+                assert_eq!(rendered, computed_args);
+                assert_eq!(rendered.line_count, 17);
+
+                let bounding_box = rendered.bounding_box.unwrap();
+                assert!(rect.contains(bounding_box.top_left));
+                assert!(rect.contains(bounding_box.bottom_right().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn wrapped_single_char() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let rect = Rectangle::new(Point::new(0, 0), Size::new(20, 20));
+
+        assert_eq!(
+            font.get_rendered_dimensions_wrapped(
+                'A',
+                rect,
+                VerticalPosition::Top,
+                WrappedAlignment::Left
+            )
+            .unwrap(),
+            RenderedWrappedDimensions {
+                bounding_box: font
+                    .get_rendered_dimensions('A', Point::new(0, -1), VerticalPosition::Top)
+                    .unwrap()
+                    .bounding_box,
+                line_count: 1,
+            }
+        );
+    }
+}
+
+mod fallback {
+    use super::*;
+
+    #[test]
+    fn background_color_requires_support_of_all_fallback_fonts() {
+        static TRANSPARENT: FontRenderer = FontRenderer::new::<fonts::u8g2_font_wqy12_t_chinese1>();
+        static SOLID: FontRenderer = FontRenderer::new::<fonts::u8g2_font_10x20_mf>();
+        let color = FontColor::WithBackground {
+            fg: Rgb888::CSS_RED,
+            bg: Rgb888::CSS_BLUE,
+        };
+
+        let render = |font: &FontRenderer, text| {
+            let mut display = TestDrawTarget::new(Size::new(100, 30));
+            font.render(
+                text,
+                Point::new(5, 20),
+                VerticalPosition::Baseline,
+                color,
+                &mut display,
+            )
+            .map(|_| ())
+        };
+
+        let font = FontRenderer::new::<fonts::u8g2_font_10x20_mr>();
+        assert!(render(&font.clone().with_fallback(&SOLID), "aä").is_ok());
+
+        let font = font.with_fallback(&TRANSPARENT);
+        assert!(matches!(
+            render(&font, "a你"),
+            Err(Error::BackgroundColorNotSupported)
+        ));
+        assert!(render(&font.with_emulated_background(true), "a你").is_ok());
+
+        // Deeper in the chain
+        static CHAIN: FontRenderer =
+            FontRenderer::new::<fonts::u8g2_font_10x20_mf>().with_fallback(&TRANSPARENT);
+        let font = FontRenderer::new::<fonts::u8g2_font_10x20_mr>().with_fallback(&CHAIN);
+        assert!(matches!(
+            render(&font, "a你"),
+            Err(Error::BackgroundColorNotSupported)
+        ));
+    }
+
+    #[test]
+    fn render_text_with_fallback_fonts() {
+        static CHINESE: FontRenderer = FontRenderer::new::<fonts::u8g2_font_wqy12_t_chinese1>();
+        static CYRILLIC: FontRenderer =
+            FontRenderer::new::<fonts::u8g2_font_6x13_t_cyrillic>().with_fallback(&CHINESE);
+
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_fallback.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_6x13_tr>()
+                    .with_fallback(&CYRILLIC)
+                    .render(
+                        "Hi Привет 你好!",
+                        Point::new(2, 15),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(90, 0),
+                bounding_box: Some(Rectangle::new(Point::new(2, 5), Size::new(87, 12)))
+            }
+        );
+    }
+
+    #[test]
+    fn fallback_fonts_report_glyphs_missing_in_all_fonts() {
+        let cyrillic = FontRenderer::new::<fonts::u8g2_font_6x13_t_cyrillic>();
+        let font = FontRenderer::new::<fonts::u8g2_font_6x13_tr>().with_fallback(&cyrillic);
+
+        assert!(font
+            .get_rendered_dimensions("Hi Привет", Point::new(0, 0), VerticalPosition::Baseline)
+            .is_ok());
+        assert!(matches!(
+            font.get_rendered_dimensions("你", Point::new(0, 0), VerticalPosition::Baseline),
+            Err(LookupError::GlyphNotFound('你'))
+        ));
+
+        let font = font.with_ignore_unknown_chars(true);
+        assert_eq!(
+            font.get_rendered_dimensions("你", Point::new(0, 0), VerticalPosition::Baseline)
+                .unwrap(),
+            RenderedDimensions::empty()
+        );
+    }
+
+    #[test]
+    fn fallback_fonts_use_metrics_of_primary_font() {
+        let large = FontRenderer::new::<fonts::u8g2_font_10x20_t_cyrillic>();
+        let font = FontRenderer::new::<fonts::u8g2_font_6x13_tr>().with_fallback(&large);
+
+        assert_eq!(
+            font.get_rendered_dimensions("П\nП", Point::new(0, 0), VerticalPosition::Top)
+                .unwrap(),
+            large
+                .clone()
+                .with_line_height(font.get_default_line_height())
+                .get_rendered_dimensions(
+                    "П\nП",
+                    Point::new(0, font.get_scaled_ascent() - large.get_scaled_ascent()),
+                    VerticalPosition::Top
+                )
+                .unwrap()
+        );
+    }
+}
+
+mod replacement {
+    use super::*;
+
+    #[test]
+    fn unknown_chars_render_as_replacement_char() {
+        let font = FontRenderer::new::<fonts::u8g2_font_6x13_tr>()
+            .with_ignore_unknown_chars(true)
+            .with_replacement_char(Some('?'));
+
+        assert_eq!(
+            font.get_rendered_dimensions("a☃b", Point::new(2, 15), VerticalPosition::Baseline)
+                .unwrap(),
+            font.get_rendered_dimensions("a?b", Point::new(2, 15), VerticalPosition::Baseline)
+                .unwrap()
+        );
+        assert_eq!(
+            font.get_rendered_dimensions_aligned(
+                "a☃b",
+                Point::new(50, 15),
+                VerticalPosition::Center,
+                HorizontalAlignment::Right
+            )
+            .unwrap(),
+            font.get_rendered_dimensions_aligned(
+                "a?b",
+                Point::new(50, 15),
+                VerticalPosition::Center,
+                HorizontalAlignment::Right
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn render_text_with_tofu() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_tofu.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_6x13_tr>()
+                    .with_replacement_char(Some('\u{FFFD}'))
+                    .render(
+                        "Snow: ☃!",
+                        Point::new(2, 15),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(48, 0),
+                bounding_box: Some(Rectangle::new(Point::new(2, 4), Size::new(45, 13)))
+            }
+        );
+    }
+
+    #[test]
+    fn render_text_with_tofu_rotated_with_background_color() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_tofu_background.png"),
+            |display| {
+                FontRenderer::new::<fonts::u8g2_font_5x7_mr>()
+                    .with_replacement_char(Some('\u{FFFD}'))
+                    .with_rotation(FontRotation::Rotate90)
+                    .with_scale(2)
+                    .render(
+                        "a☃b",
+                        Point::new(15, 3),
+                        VerticalPosition::Baseline,
+                        FontColor::WithBackground {
+                            fg: Rgb888::new(237, 28, 36),
+                            bg: Rgb888::new(63, 72, 204),
+                        },
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(0, 32),
+                bounding_box: Some(Rectangle::new(Point::new(13, 3), Size::new(14, 32)))
+            }
+        );
+    }
+
+    #[test]
+    fn tofu_dimensions_match_rendered_dimensions() {
+        let font =
+            FontRenderer::new::<fonts::u8g2_font_6x13_tr>().with_replacement_char(Some('\u{FFFD}'));
+
+        let mut display = TestDrawTarget::new(Size::new(100, 100));
+        let rendered = font
+            .render_wrapped(
+                "Tofu ☃☃☃ in a wrapped ☃ text",
+                Rectangle::new(Point::new(5, 5), Size::new(50, 90)),
+                VerticalPosition::Center,
+                WrappedAlignment::Justify,
+                FontColor::Transparent(Rgb888::CSS_RED),
+                &mut display,
+            )
+            .unwrap();
+
+        assert_eq!(
+            rendered,
+            font.get_rendered_dimensions_wrapped(
+                "Tofu ☃☃☃ in a wrapped ☃ text",
+                Rectangle::new(Point::new(5, 5), Size::new(50, 90)),
+                VerticalPosition::Center,
+                WrappedAlignment::Justify,
+            )
+            .unwrap()
+        );
+        assert_eq!(rendered.line_count, 4);
+
+        assert_consistent(&font, "Tofu ☃☃☃ in a wrapped ☃ text");
+    }
+}
+
+mod glyph_index {
+    use super::*;
+
+    #[test]
+    fn glyph_index_does_not_change_lookup_results() {
+        let fonts_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/fonts");
+
+        for entry in std::fs::read_dir(fonts_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "u8g2font") {
+                let data = std::fs::read(&path).unwrap();
+                let index = GlyphIndex::<16>::from_data(&data);
+                let font = FontRenderer::from_data(&data);
+                let indexed_font = font.clone().with_glyph_index(&index);
+
+                for ch in (0x100..=0xffff).step_by(331).filter_map(char::from_u32) {
+                    assert_eq!(
+                        font.get_rendered_dimensions(ch, Point::zero(), VerticalPosition::Baseline)
+                            .ok(),
+                        indexed_font
+                            .get_rendered_dimensions(ch, Point::zero(), VerticalPosition::Baseline)
+                            .ok(),
+                        "{} differs for {:?}",
+                        path.display(),
+                        ch
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn glyph_index_finds_all_glyphs_of_large_font() {
+        static INDEX: GlyphIndex<4096> = GlyphIndex::new::<fonts::u8g2_font_wqy12_t_gb2312>();
+        let font = FontRenderer::new::<fonts::u8g2_font_wqy12_t_gb2312>();
+        let indexed_font = font.clone().with_glyph_index(&INDEX);
+
+        for ch in ('\u{4e00}'..='\u{4fff}').chain("你好，世界！".chars()) {
+            assert_eq!(
+                font.get_rendered_dimensions(ch, Point::zero(), VerticalPosition::Baseline)
+                    .ok(),
+                indexed_font
+                    .get_rendered_dimensions(ch, Point::zero(), VerticalPosition::Baseline)
+                    .ok(),
+                "differs for {:?}",
+                ch
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn glyph_index_of_different_font_panics() {
+        let index = GlyphIndex::<16>::new::<fonts::u8g2_font_wqy12_t_chinese1>();
+        let _ = FontRenderer::new::<fonts::u8g2_font_wqy12_t_gb2312>().with_glyph_index(&index);
+    }
+}
+
+mod kerning {
+    use super::*;
+
+    #[test]
+    fn render_text_with_kerning() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_kerning.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+                font.render(
+                    "AVA To Wa",
+                    Point::new(2, 18),
+                    VerticalPosition::Baseline,
+                    FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                    display,
+                )
+                .unwrap();
+                font.with_kerning(&KERNING)
+                    .render(
+                        "AVA To Wa",
+                        Point::new(2, 40),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            RenderedDimensions {
+                advance: Point::new(96, 0),
+                bounding_box: Some(Rectangle::new(Point::new(1, 26), Size::new(96, 14)))
+            }
+        );
+    }
+
+    #[test]
+    fn kerning_is_applied_consistently() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&KERNING);
+        let text = "AVAVA To\nWa VA\nTo AV To AV Wa";
+
+        assert_consistent(&font, text);
+
+        // The kerned text fits exactly into the rectangle; unkerned, it would wrap.
+        let width = font
+            .get_rendered_dimensions("AVAVA To", Point::zero(), VerticalPosition::Baseline)
+            .unwrap()
+            .bounding_box
+            .unwrap()
+            .size
+            .width;
+        let rect = Rectangle::new(Point::new(3, 3), Size::new(width, 100));
+        let mut display = TestDrawTarget::new(Size::new(200, 110));
+        let rendered = font
+            .render_wrapped(
+                "AVAVA To AVAVA To",
+                rect,
+                VerticalPosition::Top,
+                WrappedAlignment::Justify,
+                FontColor::Transparent(Rgb888::CSS_RED),
+                &mut display,
+            )
+            .unwrap();
+        assert_eq!(rendered.line_count, 2);
+        assert_eq!(
+            rendered,
+            font.get_rendered_dimensions_wrapped(
+                "AVAVA To AVAVA To",
+                rect,
+                VerticalPosition::Top,
+                WrappedAlignment::Justify
+            )
+            .unwrap()
+        );
+        assert_eq!(rendered.bounding_box.unwrap().size.width, width);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted_kerning_pairs_panic() {
+        let _ = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&[
+            KerningPair::new('V', 'A', -3),
+            KerningPair::new('A', 'V', -3),
+        ]);
+    }
+}
+
+mod truncation {
+    use super::*;
+
+    #[test]
+    fn render_truncated() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_truncated.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+                let full = font
+                    .render_truncated(
+                        "Hello world!",
+                        Point::new(2, 2),
+                        100,
+                        VerticalPosition::Top,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap();
+                let dots = font
+                    .render_truncated(
+                        "Hello world!",
+                        Point::new(2, 14),
+                        40,
+                        VerticalPosition::Top,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap();
+                let ellipsis = FontRenderer::new::<fonts::u8g2_font_wqy12_t_gb2312>()
+                    .render_truncated(
+                        "Hello world!",
+                        Point::new(2, 26),
+                        40,
+                        VerticalPosition::Top,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap();
+                [full, dots, ellipsis]
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            [
+                RenderedTruncatedDimensions {
+                    advance: Point::new(55, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(3, 3), Size::new(53, 8))),
+                    truncated: false,
+                },
+                RenderedTruncatedDimensions {
+                    advance: Point::new(35, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(3, 15), Size::new(33, 8))),
+                    truncated: true,
+                },
+                RenderedTruncatedDimensions {
+                    advance: Point::new(35, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(2, 27), Size::new(35, 8))),
+                    truncated: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_dimensions_match_rendered_dimensions() {
+        let text = "Lorem ipsum dolor sit amet";
+
+        for (rotation, scale) in [
+            (FontRotation::Rotate0, 1),
+            (FontRotation::Rotate90, 2),
+            (FontRotation::Rotate180, 1),
+            (FontRotation::Rotate270, 3),
+        ] {
+            let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(rotation)
+                .with_scale(scale);
+
+            for max_width in (0..100).step_by(7) {
+                let mut display = TestDrawTarget::new(Size::new(400, 400));
+                let rendered = font
+                    .render_truncated(
+                        text,
+                        Point::new(200, 200),
+                        max_width,
+                        VerticalPosition::Center,
+                        FontColor::Transparent(Rgb888::CSS_RED),
+                        &mut display,
+                    )
+                    .unwrap();
+
+                let computed = font
+                    .get_rendered_dimensions_truncated(
+                        text,
+                        Point::new(200, 200),
+                        max_width,
+                        VerticalPosition::Center,
+                    )
+                    .unwrap();
+                let computed_args = font
+                    .get_rendered_dimensions_truncated(
+                        format_args!("{}", text),
+                        Point::new(200, 200),
+                        max_width,
+                        VerticalPosition::Center,
+                    )
+                    .unwrap();
+
+                assert_eq!(rendered, computed);
+                assert_eq!(rendered, computed_args);
+                assert!(rendered.truncated);
+
+                let width = rendered.bounding_box.map_or(0, |b| match rotation {
+                    FontRotation::Rotate0 | FontRotation::Rotate180 => b.size.width,
+                    FontRotation::Rotate90 | FontRotation::Rotate270 => b.size.height,
+                });
+                assert!(width <= max_width);
+            }
+        }
+    }
+
+    #[test]
+    fn untruncated_text_renders_like_render() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+
+        let truncated = font
+            .get_rendered_dimensions_truncated(
+                format_args!("Answer: {}", 42),
+                Point::new(3, 4),
+                u32::MAX,
+                VerticalPosition::Bottom,
+            )
+            .unwrap();
+        let full = font
+            .get_rendered_dimensions(
+                format_args!("Answer: {}", 42),
+                Point::new(3, 4),
+                VerticalPosition::Bottom,
+            )
+            .unwrap();
+
+        assert!(!truncated.truncated);
+        assert_eq!(truncated.advance, full.advance);
+        assert_eq!(truncated.bounding_box, full.bounding_box);
+    }
+
+    #[test]
+    fn truncates_at_newline() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+
+        let truncated = font
+            .get_rendered_dimensions_truncated(
+                "Hello\nworld!",
+                Point::new(0, 0),
+                100,
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+        let expected = font
+            .get_rendered_dimensions("Hello...", Point::new(0, 0), VerticalPosition::Baseline)
+            .unwrap();
+
+        assert!(truncated.truncated);
+        assert_eq!(truncated.advance, expected.advance);
+        assert_eq!(truncated.bounding_box, expected.bounding_box);
+    }
+
+    #[test]
+    fn truncation_uses_ellipsis_of_fallback_font() {
+        static FALLBACK: FontRenderer = FontRenderer::new::<fonts::u8g2_font_wqy12_t_gb2312>();
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>().with_fallback(&FALLBACK);
+
+        let truncated = font
+            .get_rendered_dimensions_truncated(
+                "Hello world!",
+                Point::new(0, 0),
+                40,
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+        let expected = font
+            .get_rendered_dimensions(
+                "Hello \u{2026}",
+                Point::new(0, 0),
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+
+        assert!(truncated.truncated);
+        assert_eq!(truncated.advance, expected.advance);
+        assert_eq!(truncated.bounding_box, expected.bounding_box);
+    }
+
+    #[test]
+    fn truncation_without_ellipsis_glyphs() {
+        let font = FontRenderer::new::<fonts::u8g2_font_open_iconic_all_1x_t>();
+        assert!(!font.has_glyph('.') && !font.has_glyph('\u{2026}'));
+        let text: String = font.encodings().skip(20).take(5).collect();
+
+        // Text that fits doesn't need an ellipsis
+        let untruncated = font
+            .get_rendered_dimensions_truncated(
+                &*text,
+                Point::new(0, 0),
+                100,
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+        let expected = font
+            .get_rendered_dimensions(&*text, Point::new(0, 0), VerticalPosition::Baseline)
+            .unwrap();
+        assert!(!untruncated.truncated);
+        assert_eq!(untruncated.advance, expected.advance);
+
+        let truncated = font
+            .get_rendered_dimensions_truncated(
+                &*text,
+                Point::new(0, 0),
+                20,
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+        let expected = font
+            .get_rendered_dimensions(
+                &text[..text.char_indices().nth(2).unwrap().0],
+                Point::new(0, 0),
+                VerticalPosition::Baseline,
+            )
+            .unwrap();
+        assert!(truncated.truncated);
+        assert_eq!(truncated.advance, expected.advance);
+        assert_eq!(truncated.bounding_box, expected.bounding_box);
+
+        let mut display = TestDrawTarget::new(Size::new(50, 20));
+        let rendered = font
+            .render_truncated(
+                &*text,
+                Point::new(0, 10),
+                20,
+                VerticalPosition::Baseline,
+                FontColor::Transparent(Rgb888::CSS_RED),
+                &mut display,
+            )
+            .unwrap();
+        assert!(rendered.truncated);
+    }
+}
+
+mod spacing {
+    use super::*;
+
+    #[test]
+    fn render_text_with_spacing() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_spacing.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+                [
+                    font.clone(),
+                    font.clone().with_letter_spacing(1),
+                    font.with_word_spacing(4),
+                ]
+                .iter()
+                .zip([10, 22, 34])
+                .map(|(font, y)| {
+                    font.render(
+                        "Hello world!",
+                        Point::new(2, y),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+                    .advance
+                })
+                .collect::<Vec<_>>()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            [Point::new(55, 0), Point::new(67, 0), Point::new(59, 0)]
+        );
+    }
+
+    #[test]
+    fn spacing_is_applied_consistently() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+            .with_letter_spacing(2)
+            .with_word_spacing(-1);
+        let text = "Lorem ipsum\ndolor sit amet,\nconsectetur";
+
+        assert_consistent(&font, text);
+
+        // Every character of a line moves by the letter spacing, spaces additionally by the word spacing
+        let unspaced = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+            .get_rendered_dimensions("dolor sit amet,", Point::zero(), VerticalPosition::Baseline)
+            .unwrap();
+        let spaced = font
+            .get_rendered_dimensions("dolor sit amet,", Point::zero(), VerticalPosition::Baseline)
+            .unwrap();
+        assert_eq!(spaced.advance.x, unspaced.advance.x + 15 * 2 - 2);
+    }
+}
+
+mod synthetic_styles {
+    use super::*;
+
+    #[test]
+    fn render_text_with_synthetic_styles() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_synthetic_styles.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+                [
+                    font.clone(),
+                    font.clone().with_synthetic_bold(true),
+                    font.clone().with_synthetic_italic(true),
+                    font.with_synthetic_bold(true).with_synthetic_italic(true),
+                ]
+                .iter()
+                .zip([11, 25, 39, 53])
+                .map(|(font, y)| {
+                    font.render(
+                        "Quick fox jumps",
+                        Point::new(2, y),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                        display,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            [
+                RenderedDimensions {
+                    advance: Point::new(73, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(3, 3), Size::new(71, 10)))
+                },
+                RenderedDimensions {
+                    advance: Point::new(88, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(3, 17), Size::new(86, 10)))
+                },
+                RenderedDimensions {
+                    advance: Point::new(86, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(2, 31), Size::new(85, 10)))
+                },
+                RenderedDimensions {
+                    advance: Point::new(101, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(2, 45), Size::new(100, 10)))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_text_with_synthetic_styles_and_background_color() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_synthetic_styles_background.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_10x20_mf>()
+                    .with_synthetic_bold(true)
+                    .with_synthetic_italic(true);
+                let color = FontColor::WithBackground {
+                    fg: Rgb888::new(237, 28, 36),
+                    bg: Rgb888::new(1, 1, 1),
+                };
+                [
+                    font.render(
+                        "Hello, W0rld!",
+                        Point::new(2, 20),
+                        VerticalPosition::default(),
+                        color,
+                        display,
+                    )
+                    .unwrap(),
+                    font.with_rotation(FontRotation::Rotate180)
+                        .render(
+                            "gy",
+                            Point::new(100, 46),
+                            VerticalPosition::Top,
+                            color,
+                            display,
+                        )
+                        .unwrap(),
+                ]
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            [
+                RenderedDimensions {
+                    advance: Point::new(182, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(1, 4), Size::new(183, 20)))
+                },
+                RenderedDimensions {
+                    advance: Point::new(-28, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(72, 28), Size::new(29, 20)))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn synthetic_styles_stay_inside_bounding_box() {
+        let text = "AjW@g|y";
+
+        for (rotation, scale) in [
+            (FontRotation::Rotate0, 1),
+            (FontRotation::Rotate90, 2),
+            (FontRotation::Rotate180, 1),
+            (FontRotation::Rotate270, 3),
+        ] {
+            for (bold, italic) in [(true, false), (false, true), (true, true)] {
+                let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>()
+                    .with_rotation(rotation)
+                    .with_scale(scale)
+                    .with_synthetic_bold(bold)
+                    .with_synthetic_italic(italic);
+
+                assert_consistent(&font, text);
+                for ch in text.chars() {
+                    assert_consistent(&font, ch);
+                }
+            }
+        }
+    }
+}
+
+mod decorations {
+    use super::*;
+
+    #[test]
+    fn render_text_with_decorations() {
+        let dimensions = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_decorations.png"),
+            |display| {
+                let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+                [
+                    TextDecorations::NONE.with_underline(true),
+                    TextDecorations::NONE.with_strikethrough(true),
+                    TextDecorations::NONE
+                        .with_underline(true)
+                        .with_strikethrough(true),
+                ]
+                .into_iter()
+                .zip([11, 25, 39])
+                .map(|(decorations, y)| {
+                    font.clone()
+                        .with_decorations(decorations)
+                        .render(
+                            "Jumpy fox, quick!",
+                            Point::new(2, y),
+                            VerticalPosition::Baseline,
+                            FontColor::Transparent(Rgb888::new(237, 28, 36)),
+                            display,
+                        )
+                        .unwrap()
+                        .bounding_box
+                })
+                .collect::<Vec<_>>()
+            },
+        );
+
+        assert_eq!(
+            dimensions,
+            [
+                Some(Rectangle::new(Point::new(2, 3), Size::new(80, 10))),
+                Some(Rectangle::new(Point::new(2, 17), Size::new(80, 10))),
+                Some(Rectangle::new(Point::new(2, 31), Size::new(80, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn decorations_are_applied_consistently() {
+        let text = "  Lorem ipsum\ndolor sit amet,\nconsectetur  ";
+
+        for (rotation, scale) in [(FontRotation::Rotate0, 1), (FontRotation::Rotate90, 2)] {
+            let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(rotation)
+                .with_scale(scale)
+                .with_decorations(
+                    TextDecorations::NONE
+                        .with_underline(true)
+                        .with_strikethrough(true),
+                );
+
+            assert_consistent(&font, text);
+        }
+    }
+
+    #[test]
+    fn decorations_span_whitespace() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let underlined = font
+            .clone()
+            .with_decorations(TextDecorations::NONE.with_underline(true));
+
+        let plain = font
+            .get_rendered_dimensions("  ", Point::new(5, 10), VerticalPosition::Baseline)
+            .unwrap();
+        let decorated = underlined
+            .get_rendered_dimensions("  ", Point::new(5, 10), VerticalPosition::Baseline)
+            .unwrap();
+
+        assert_eq!(plain.bounding_box, None);
+        assert_eq!(decorated.advance, plain.advance);
+        assert_eq!(
+            decorated.bounding_box,
+            Some(Rectangle::new(
+                Point::new(5, 11),
+                Size::new(plain.advance.x as u32, 1)
+            ))
+        );
+    }
+}
+
+mod emulated_background {
+    use super::*;
+
+    #[test]
+    fn render_text_with_emulated_background() {
+        let color = FontColor::WithBackground {
+            fg: Rgb888::new(237, 28, 36),
+            bg: Rgb888::new(63, 72, 204),
+        };
+        let font =
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>().with_emulated_background(true);
+        let large =
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_emulated_background(true);
+
+        TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_emulated_background.png"),
+            |display| {
+                font.render(
+                    "Hello\nworld!",
+                    Point::new(2, 10),
+                    VerticalPosition::Baseline,
+                    color,
+                    display,
+                )
+                .unwrap();
+                large
+                    .render_aligned(
+                        "Label",
+                        Point::new(90, 4),
+                        VerticalPosition::Top,
+                        HorizontalAlignment::Center,
+                        color,
+                        display,
+                    )
+                    .unwrap();
+                font.render_wrapped(
+                    "Wrapped text that is justified",
+                    Rectangle::new(Point::new(2, 30), Size::new(60, 40)),
+                    VerticalPosition::Top,
+                    WrappedAlignment::Justify,
+                    color,
+                    display,
+                )
+                .unwrap();
+                font.clone()
+                    .with_rotation(FontRotation::Rotate90)
+                    .render_truncated(
+                        "Truncated text",
+                        Point::new(110, 28),
+                        40,
+                        VerticalPosition::Baseline,
+                        color,
+                        display,
+                    )
+                    .unwrap();
+            },
+        );
+    }
+
+    #[test]
+    fn emulated_background_is_applied_consistently() {
+        let fg = Rgb888::CSS_RED;
+        let bg = Rgb888::CSS_BLUE;
+        let color = FontColor::WithBackground { fg, bg };
+        let font =
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>().with_emulated_background(true);
+        let position = Point::new(10, 20);
+
+        assert_consistent(&font, "Hi j\nHi j");
+
+        // Without emulation, the font doesn't support a background color
+        assert!(matches!(
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>().render(
+                "Hi",
+                position,
+                VerticalPosition::Baseline,
+                color,
+                &mut TestDrawTarget::new(Size::new(64, 32)),
+            ),
+            Err(Error::BackgroundColorNotSupported)
+        ));
+
+        let mut display = TestDrawTarget::new(Size::new(64, 32));
+        let rendered = font
+            .render(
+                "Hi j",
+                position,
+                VerticalPosition::Baseline,
+                color,
+                &mut display,
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            font.get_rendered_dimensions("Hi j", position, VerticalPosition::Baseline)
+                .unwrap()
+        );
+
+        // The line box spans the advance and the font bounding box, and contains all glyphs
+        let font_bounding_box = font.get_font_bounding_box(VerticalPosition::Baseline);
+        let line_box = Rectangle::new(
+            Point::new(position.x, position.y + font_bounding_box.top_left.y),
+            Size::new(rendered.advance.x as u32, font_bounding_box.size.height),
+        );
+        for y in 0..32 {
+            for x in 0..64 {
+                let p = Point::new(x, y);
+                let pixel = display.get_pixel(p);
+                if line_box.contains(p) {
+                    assert!(pixel == Some(fg) || pixel == Some(bg), "{:?}", p);
+                } else {
+                    assert_eq!(pixel, Some(Rgb888::CSS_WHITE), "{:?}", p);
+                }
+            }
+        }
+
+        let mut display = TestDrawTarget::new(Size::new(64, 32));
+        let rendered = font
+            .render_truncated(
+                "Hi j",
+                position,
+                20,
+                VerticalPosition::Baseline,
+                color,
+                &mut display,
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            font.get_rendered_dimensions_truncated(
+                "Hi j",
+                position,
+                20,
+                VerticalPosition::Baseline
+            )
+            .unwrap()
+        );
+
+        let text_box = Rectangle::new(Point::new(2, 2), Size::new(30, 28));
+        let mut display = TestDrawTarget::new(Size::new(64, 32));
+        let rendered = font
+            .render_wrapped(
+                "Hi j Hi j",
+                text_box,
+                VerticalPosition::Top,
+                WrappedAlignment::Center,
+                color,
+                &mut display,
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            font.get_rendered_dimensions_wrapped(
+                "Hi j Hi j",
+                text_box,
+                VerticalPosition::Top,
+                WrappedAlignment::Center
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn passes_on_error_with_emulated_background() {
+        let font =
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_emulated_background(true);
+        let position = Point::new(1, 1);
+        let hpos = HorizontalAlignment::Center;
+        let vpos = VerticalPosition::Center;
+        let color = FontColor::WithBackground {
+            fg: Rgb888::CSS_ALICE_BLUE,
+            bg: Rgb888::CSS_BLACK,
+        };
+        let text_box = Rectangle::new(Point::zero(), Size::new(10, 10));
+
+        FailingDrawTarget::assert_passes_on_error(|display| {
+            font.render("a", position, vpos, color, display)
+        });
+        FailingDrawTarget::assert_passes_on_error(|display| {
+            font.render_aligned(format_args!("{}", 1), position, vpos, hpos, color, display)
+        });
+        FailingDrawTarget::assert_passes_on_error(|display| {
+            font.render_wrapped("a", text_box, vpos, hpos.into(), color, display)
+        });
+        FailingDrawTarget::assert_passes_on_error(|display| {
+            font.render_truncated("a", position, 50, vpos, color, display)
+        });
+    }
+}
+
+mod effects {
+    use super::*;
+
+    #[test]
+    fn render_text_with_effects() {
+        let outline = FontColor::WithOutline {
+            fg: Rgb888::new(237, 28, 36),
+            outline: Rgb888::new(255, 255, 255),
+        };
+        let shadow = FontColor::WithShadow {
+            fg: Rgb888::new(237, 28, 36),
+            shadow: Rgb888::new(0, 0, 0),
+        };
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        let small = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+            .with_decorations(TextDecorations::NONE.with_underline(true));
+
+        let bounding_boxes = TestDrawTarget::expect_image(
+            std::include_bytes!("assets/render_text_effects.png"),
+            |display| {
+                // A busy background
+                for x in (0..display.size().width as i32).step_by(4) {
+                    display
+                        .fill_solid(
+                            &Rectangle::new(Point::new(x, 0), Size::new(2, display.size().height)),
+                            Rgb888::new(63, 72, 204),
+                        )
+                        .unwrap();
+                }
+
+                let outlined = font
+                    .render(
+                        "Outline",
+                        Point::new(4, 4),
+                        VerticalPosition::Top,
+                        outline,
+                        display,
+                    )
+                    .unwrap();
+                let shadowed = font
+                    .render_aligned(
+                        "Shadow",
+                        Point::new(64, 24),
+                        VerticalPosition::Top,
+                        HorizontalAlignment::Center,
+                        shadow,
+                        display,
+                    )
+                    .unwrap();
+                let wrapped = small
+                    .render_wrapped(
+                        "Wrapped outline",
+                        Rectangle::new(Point::new(4, 44), Size::new(120, 20)),
+                        VerticalPosition::Top,
+                        WrappedAlignment::Left,
+                        outline,
+                        display,
+                    )
+                    .unwrap();
+
+                (outlined, shadowed, wrapped)
+            },
+        );
+
+        assert_eq!(
+            bounding_boxes,
+            (
+                RenderedDimensions {
+                    advance: Point::new(73, 0),
+                    bounding_box: Some(Rectangle::new(Point::new(3, 4), Size::new(74, 16)))
+                },
+                Some(Rectangle::new(Point::new(26, 25), Size::new(77, 15))),
+                RenderedWrappedDimensions {
+                    bounding_box: Some(Rectangle::new(Point::new(3, 43), Size::new(76, 12))),
+                    line_count: 1
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn effects_grow_bounding_boxes() {
+        let fg = Rgb888::CSS_RED;
+        let effect = Rgb888::CSS_BLUE;
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+            .with_rotation(FontRotation::Rotate90);
+        let position = Point::new(20, 10);
+        let text_box = Rectangle::new(Point::new(2, 2), Size::new(40, 60));
+        let text = "Ag j";
+
+        let grow = |rect: Rectangle, top_left: Point, bottom_right: Point| {
+            Rectangle::with_corners(
+                rect.top_left + top_left,
+                rect.bottom_right().unwrap() + bottom_right,
+            )
+        };
+
+        // Rotated by 90 degrees, right in text space is down on the display
+        for (color, top_left, bottom_right) in [
+            (
+                FontColor::WithOutline {
+                    fg,
+                    outline: effect,
+                },
+                Point::new(-1, -1),
+                Point::new(1, 1),
+            ),
+            (
+                FontColor::WithShadow { fg, shadow: effect },
+                Point::new(-1, 0),
+                Point::new(0, 1),
+            ),
+        ] {
+            let check_pixels = |display: &TestDrawTarget, bounding_box: Rectangle| {
+                for y in 0..64 {
+                    for x in 0..64 {
+                        let p = Point::new(x, y);
+                        if display.get_pixel(p) != Some(Rgb888::CSS_WHITE) {
+                            assert!(bounding_box.contains(p), "{:?}", p);
+                        }
+                    }
+                }
+            };
+
+            let mut display = TestDrawTarget::new(Size::new(64, 64));
+            let rendered = font
+                .render(
+                    text,
+                    position,
+                    VerticalPosition::Baseline,
+                    color,
+                    &mut display,
+                )
+                .unwrap();
+            let computed = font
+                .get_rendered_dimensions(text, position, VerticalPosition::Baseline)
+                .unwrap();
+            assert_eq!(rendered.advance, computed.advance);
+            assert_eq!(
+                rendered.bounding_box,
+                computed
+                    .bounding_box
+                    .map(|b| grow(b, top_left, bottom_right))
+            );
+            check_pixels(&display, rendered.bounding_box.unwrap());
+
+            let mut display = TestDrawTarget::new(Size::new(64, 64));
+            let rendered = font
+                .render_aligned(
+                    text,
+                    position,
+                    VerticalPosition::Baseline,
+                    HorizontalAlignment::Right,
+                    color,
+                    &mut display,
+                )
+                .unwrap();
+            let computed = font
+                .get_rendered_dimensions_aligned(
+                    text,
+                    position,
+                    VerticalPosition::Baseline,
+                    HorizontalAlignment::Right,
+                )
+                .unwrap();
+            assert_eq!(rendered, computed.map(|b| grow(b, top_left, bottom_right)));
+            check_pixels(&display, rendered.unwrap());
+
+            let mut display = TestDrawTarget::new(Size::new(64, 64));
+            let rendered = font
+                .render_wrapped(
+                    text,
+                    text_box,
+                    VerticalPosition::Top,
+                    WrappedAlignment::Center,
+                    color,
+                    &mut display,
+                )
+                .unwrap();
+            let computed = font
+                .get_rendered_dimensions_wrapped(
+                    text,
+                    text_box,
+                    VerticalPosition::Top,
+                    WrappedAlignment::Center,
+                )
+                .unwrap();
+            assert_eq!(rendered.line_count, computed.line_count);
+            assert_eq!(
+                rendered.bounding_box,
+                computed
+                    .bounding_box
+                    .map(|b| grow(b, top_left, bottom_right))
+            );
+            check_pixels(&display, rendered.bounding_box.unwrap());
+
+            let mut display = TestDrawTarget::new(Size::new(64, 64));
+            let rendered = font
+                .render_truncated(
+                    text,
+                    position,
+                    15,
+                    VerticalPosition::Baseline,
+                    color,
+                    &mut display,
+                )
+                .unwrap();
+            let computed = font
+                .get_rendered_dimensions_truncated(text, position, 15, VerticalPosition::Baseline)
+                .unwrap();
+            assert_eq!(rendered.advance, computed.advance);
+            assert_eq!(rendered.truncated, computed.truncated);
+            assert_eq!(
+                rendered.bounding_box,
+                computed
+                    .bounding_box
+                    .map(|b| grow(b, top_left, bottom_right))
+            );
+            check_pixels(&display, rendered.bounding_box.unwrap());
+        }
+    }
+
+    #[test]
+    fn effect_bounding_box_matches_rendering() {
+        let fg = Rgb888::CSS_RED;
+        let effect = Rgb888::CSS_BLUE;
+        let position = Point::new(100, 100);
+        let text = "Ag j\nfx";
+
+        for rotation in [
+            FontRotation::Rotate0,
+            FontRotation::Rotate90,
+            FontRotation::Rotate180,
+            FontRotation::Rotate270,
+        ] {
+            for scale in [1, 2, 3] {
+                let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                    .with_rotation(rotation)
+                    .with_scale(scale);
+
+                for color in [
+                    FontColor::Transparent(fg),
+                    FontColor::WithOutline {
+                        fg,
+                        outline: effect,
+                    },
+                    FontColor::WithShadow { fg, shadow: effect },
+                ] {
+                    let mut display = TestDrawTarget::new(Size::new(200, 200));
+                    let rendered = font
+                        .render(
+                            text,
+                            position,
+                            VerticalPosition::Baseline,
+                            color,
+                            &mut display,
+                        )
+                        .unwrap();
+                    let computed = font
+                        .get_rendered_dimensions(text, position, VerticalPosition::Baseline)
+                        .unwrap();
+                    assert_eq!(
+                        rendered.bounding_box,
+                        font.get_effect_bounding_box(computed.bounding_box, color),
+                        "{:?} {} {:?}",
+                        rotation,
+                        scale,
+                        color
+                    );
+                }
+            }
+        }
+
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let shadow = FontColor::WithShadow { fg, shadow: effect };
+        assert_eq!(font.get_effect_bounding_box(None, shadow), None);
+    }
+
+    #[test]
+    fn effects_never_cover_neighbouring_glyphs() {
+        // Italic glyphs with negative letter spacing overlap their neighbours
+        let font = FontRenderer::new::<fonts::u8g2_font_helvB08_tr>()
+            .with_synthetic_italic(true)
+            .with_letter_spacing(-2);
+        let fg = Rgb888::CSS_RED;
+
+        let mut plain = TestDrawTarget::new(Size::new(64, 16));
+        font.render(
+            "HWMill",
+            Point::new(4, 12),
+            VerticalPosition::Baseline,
+            FontColor::Transparent(fg),
+            &mut plain,
+        )
+        .unwrap();
+
+        for color in [
+            FontColor::WithOutline {
+                fg,
+                outline: Rgb888::CSS_BLUE,
+            },
+            FontColor::WithShadow {
+                fg,
+                shadow: Rgb888::CSS_BLUE,
+            },
+        ] {
+            let mut display = TestDrawTarget::new(Size::new(64, 16));
+            font.render(
+                "HWMill",
+                Point::new(4, 12),
+                VerticalPosition::Baseline,
+                color,
+                &mut display,
+            )
+            .unwrap();
+
+            for y in 0..16 {
+                for x in 0..64 {
+                    let p = Point::new(x, y);
+                    if plain.get_pixel(p) == Some(fg) {
+                        assert_eq!(display.get_pixel(p), Some(fg), "{:?}", p);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn passes_on_error_with_effects() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        let position = Point::new(1, 1);
+        let hpos = HorizontalAlignment::Center;
+        let vpos = VerticalPosition::Center;
+        let text_box = Rectangle::new(Point::zero(), Size::new(10, 10));
+
+        for color in [
+            FontColor::WithOutline {
+                fg: Rgb888::CSS_ALICE_BLUE,
+                outline: Rgb888::CSS_BLACK,
+            },
+            FontColor::WithShadow {
+                fg: Rgb888::CSS_ALICE_BLUE,
+                shadow: Rgb888::CSS_BLACK,
+            },
+        ] {
+            FailingDrawTarget::assert_passes_on_error(|display| {
+                font.render("a", position, vpos, color, display)
+            });
+            FailingDrawTarget::assert_passes_on_error(|display| {
+                font.render_aligned(format_args!("{}", 1), position, vpos, hpos, color, display)
+            });
+            FailingDrawTarget::assert_passes_on_error(|display| {
+                font.render_wrapped("a", text_box, vpos, hpos.into(), color, display)
+            });
+            FailingDrawTarget::assert_passes_on_error(|display| {
+                font.render_truncated("a", position, 50, vpos, color, display)
+            });
+        }
+    }
+}
+
+mod clipping {
+    use super::*;
+
+    #[test]
+    fn clips_glyphs_outside_of_display() {
+        let text = "Scrolling text\nthat is much longer than the display";
+        let red = Rgb888::new(237, 28, 36);
+        let blue = Rgb888::new(63, 72, 204);
+        let helv = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+
+        let cases = [
+            (
+                helv.clone()
+                    .with_decorations(TextDecorations::NONE.with_underline(true)),
+                FontColor::Transparent(red),
+            ),
+            (
+                FontRenderer::new::<fonts::u8g2_font_10x20_mf>(),
+                FontColor::WithBackground { fg: red, bg: blue },
+            ),
+            (
+                helv.clone().with_emulated_background(true),
+                FontColor::WithBackground { fg: red, bg: blue },
+            ),
+            (
+                FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>()
+                    .with_rotation(FontRotation::Rotate90)
+                    .with_scale(2),
+                FontColor::WithOutline {
+                    fg: red,
+                    outline: blue,
+                },
+            ),
+        ];
+
+        // The display is a window into a larger reference display
+        let offset = Point::new(300, 300);
+        for (font, color) in cases {
+            for position in [Point::new(-60, 12), Point::new(20, -10), Point::new(30, 40)] {
+                let mut display = TestDrawTarget::new(Size::new(64, 32));
+                let mut reference = TestDrawTarget::new(Size::new(700, 700));
+
+                let clipped = font
+                    .render(
+                        text,
+                        position,
+                        VerticalPosition::Baseline,
+                        color,
+                        &mut display,
+                    )
+                    .unwrap();
+                let full = font
+                    .render(
+                        text,
+                        position + offset,
+                        VerticalPosition::Baseline,
+                        color,
+                        &mut reference,
+                    )
+                    .unwrap();
+
+                assert_eq!(display.out_of_bounds_pixels(), 0);
+                assert_eq!(reference.out_of_bounds_pixels(), 0);
+                assert_eq!(clipped.advance, full.advance);
+                assert_eq!(
+                    clipped.bounding_box.map(|b| b.top_left + offset),
+                    full.bounding_box.map(|b| b.top_left)
+                );
+                assert_eq!(
+                    clipped.bounding_box.map(|b| b.size),
+                    full.bounding_box.map(|b| b.size)
+                );
+
+                for p in display.bounding_box().points() {
+                    assert_eq!(
+                        display.get_pixel(p),
+                        reference.get_pixel(p + offset),
+                        "{:?}",
+                        p
+                    );
+                }
+
+                let mut display = TestDrawTarget::new(Size::new(64, 32));
+                let text_box = Rectangle::new(position, Size::new(120, 200));
+                let clipped = font
+                    .render_wrapped(
+                        text,
+                        text_box,
+                        VerticalPosition::Top,
+                        WrappedAlignment::Center,
+                        color,
+                        &mut display,
+                    )
+                    .unwrap();
+                assert_eq!(display.out_of_bounds_pixels(), 0);
+                assert_eq!(
+                    clipped,
+                    font.render_wrapped(
+                        text,
+                        text_box,
+                        VerticalPosition::Top,
+                        WrappedAlignment::Center,
+                        color,
+                        &mut TestDrawTarget::new(Size::new(500, 500)),
+                    )
+                    .unwrap()
+                );
+            }
+        }
+    }
+}
+
+mod layout {
+    use super::*;
+
+    #[test]
+    fn layout_matches_rendered_dimensions() {
+        // Nested function, as every content type produces a different layout type
+        fn check<C: Content>(layout: Layout<'_, C>, expected: Option<Rectangle>) {
+            let bounding_box = layout.map(|glyph| glyph.unwrap().bounding_box).fold(
+                None,
+                |acc: Option<Rectangle>, b| match (acc, b) {
+                    (Some(acc), Some(b)) => {
+                        let (acc_br, b_br) =
+                            (acc.bottom_right().unwrap(), b.bottom_right().unwrap());
+                        Some(Rectangle::with_corners(
+                            Point::new(
+                                acc.top_left.x.min(b.top_left.x),
+                                acc.top_left.y.min(b.top_left.y),
+                            ),
+                            Point::new(acc_br.x.max(b_br.x), acc_br.y.max(b_br.y)),
+                        ))
+                    }
+                    (acc, b) => acc.or(b),
+                },
+            );
+            assert_eq!(bounding_box, expected);
+        }
+
+        let fonts = [
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&KERNING),
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(FontRotation::Rotate90)
+                .with_scale(2),
+            FontRenderer::new::<fonts::u8g2_font_10x20_mf>()
+                .with_rotation(FontRotation::Rotate270)
+                .with_decorations(TextDecorations::NONE.with_underline(true)),
+        ];
+        let position = Point::new(40, 30);
+
+        for font in fonts {
+            for horizontal_align in [
+                HorizontalAlignment::Left,
+                HorizontalAlignment::Center,
+                HorizontalAlignment::Right,
+            ] {
+                for vertical_pos in [VerticalPosition::Baseline, VerticalPosition::Center] {
+                    let text = "AVA To\n\nWa, Agi!\n";
+                    check(
+                        font.layout(text, position, vertical_pos, horizontal_align),
+                        font.get_rendered_dimensions_aligned(
+                            text,
+                            position,
+                            vertical_pos,
+                            horizontal_align,
+                        )
+                        .unwrap(),
+                    );
+                    check(
+                        font.layout(
+                            format_args!("{}\n{}", "AVA To", 42),
+                            position,
+                            vertical_pos,
+                            horizontal_align,
+                        ),
+                        font.get_rendered_dimensions_aligned(
+                            format_args!("{}\n{}", "AVA To", 42),
+                            position,
+                            vertical_pos,
+                            horizontal_align,
+                        )
+                        .unwrap(),
+                    );
+                    check(
+                        font.layout('W', position, vertical_pos, horizontal_align),
+                        font.get_rendered_dimensions_aligned(
+                            'W',
+                            position,
+                            vertical_pos,
+                            horizontal_align,
+                        )
+                        .unwrap(),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn layout_positions_match_render_aligned() {
+        let color = FontColor::Transparent(Rgb888::new(237, 28, 36));
+        let fonts = [
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>()
+                .with_kerning(&KERNING)
+                .with_letter_spacing(1),
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(FontRotation::Rotate180)
+                .with_scale(2),
+        ];
+        let text = "AVA To\nWa, Agi!";
+        let position = Point::new(60, 50);
+
+        for font in fonts {
+            for horizontal_align in [
+                HorizontalAlignment::Left,
+                HorizontalAlignment::Center,
+                HorizontalAlignment::Right,
+            ] {
+                let mut expected = TestDrawTarget::new(Size::new(120, 100));
+                font.render_aligned(
+                    text,
+                    position,
+                    VerticalPosition::Top,
+                    horizontal_align,
+                    color,
+                    &mut expected,
+                )
+                .unwrap();
+
+                let mut actual = TestDrawTarget::new(Size::new(120, 100));
+                for glyph in font.layout(text, position, VerticalPosition::Top, horizontal_align) {
+                    let glyph = glyph.unwrap();
+                    let dimensions = font
+                        .render(
+                            glyph.ch,
+                            glyph.origin,
+                            VerticalPosition::Baseline,
+                            color,
+                            &mut actual,
+                        )
+                        .unwrap();
+                    assert_eq!(dimensions.bounding_box, glyph.bounding_box);
+                    if glyph.ch != '\n' {
+                        assert_eq!(dimensions.advance, glyph.advance);
+                    }
+                }
+
+                for p in expected.bounding_box().points() {
+                    assert_eq!(actual.get_pixel(p), expected.get_pixel(p), "{:?}", p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn layout_yields_every_character() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let glyphs = font
+            .layout(
+                "A \nB",
+                Point::new(10, 20),
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left,
+            )
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            glyphs
+                .iter()
+                .map(|glyph| (glyph.index, glyph.ch))
+                .collect::<Vec<_>>(),
+            [(0, 'A'), (1, ' '), (2, '\n'), (3, 'B')]
+        );
+
+        assert_eq!(glyphs[0].origin, Point::new(10, 20));
+        assert!(glyphs[0].bounding_box.is_some());
+
+        // Whitespace advances, but isn't visible
+        assert_eq!(glyphs[1].origin, glyphs[0].origin + glyphs[0].advance);
+        assert_ne!(glyphs[1].advance, Point::zero());
+        assert_eq!(glyphs[1].bounding_box, None);
+
+        // A newline sits at the end of its line and starts the next one
+        assert_eq!(glyphs[2].origin, glyphs[1].origin + glyphs[1].advance);
+        assert_eq!(glyphs[2].advance, Point::zero());
+        assert_eq!(glyphs[2].bounding_box, None);
+        assert_eq!(
+            glyphs[3].origin,
+            Point::new(
+                10,
+                20 + i32::try_from(font.get_default_line_height()).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn layout_stops_at_lookup_error() {
+        let font = FontRenderer::new::<fonts::u8g2_font_courB10_tn>();
+
+        let mut layout = font.layout(
+            "1a2",
+            Point::zero(),
+            VerticalPosition::Baseline,
+            HorizontalAlignment::Left,
+        );
+        assert!(matches!(layout.next(), Some(Ok(glyph)) if glyph.ch == '1'));
+        assert!(matches!(
+            layout.next(),
+            Some(Err(LookupError::GlyphNotFound('a')))
+        ));
+        assert!(layout.next().is_none());
+
+        // Aligned text measures the whole line first
+        let mut layout = font.layout(
+            "1a2",
+            Point::zero(),
+            VerticalPosition::Baseline,
+            HorizontalAlignment::Center,
+        );
+        assert!(matches!(
+            layout.next(),
+            Some(Err(LookupError::GlyphNotFound('a')))
+        ));
+        assert!(layout.next().is_none());
+    }
+}
+
+mod hit_testing {
+    use super::*;
+
+    #[test]
+    fn hit_testing_matches_layout() {
+        let fonts = [
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&KERNING),
+            FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(FontRotation::Rotate90)
+                .with_scale(2),
+            FontRenderer::new::<fonts::u8g2_font_10x20_mf>().with_rotation(FontRotation::Rotate180),
+        ];
+        let text = "AVA To\n\nWa, Agi!";
+        let position = Point::new(40, 30);
+
+        for font in fonts {
+            for horizontal_align in [
+                HorizontalAlignment::Left,
+                HorizontalAlignment::Center,
+                HorizontalAlignment::Right,
+            ] {
+                let glyphs = font
+                    .layout(text, position, VerticalPosition::Center, horizontal_align)
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                let caret_position = |index| {
+                    font.caret_position(
+                        text,
+                        position,
+                        VerticalPosition::Center,
+                        horizontal_align,
+                        index,
+                    )
+                    .unwrap()
+                };
+                let char_index_at = |point| {
+                    font.char_index_at(
+                        text,
+                        position,
+                        VerticalPosition::Center,
+                        horizontal_align,
+                        point,
+                    )
+                    .unwrap()
+                };
+
+                for glyph in &glyphs {
+                    assert_eq!(caret_position(glyph.index), glyph.origin);
+                    if let Some(bounding_box) = glyph.bounding_box {
+                        assert_eq!(
+                            char_index_at(bounding_box.center()),
+                            Some(glyph.index),
+                            "{:?}",
+                            glyph
+                        );
+                    }
+                }
+
+                let last = glyphs.last().unwrap();
+                assert_eq!(caret_position(glyphs.len()), last.origin + last.advance);
+                assert_eq!(caret_position(usize::MAX), last.origin + last.advance);
+
+                // Empty lines and areas outside of the text contain no characters
+                assert_eq!(char_index_at(glyphs[7].origin), None);
+                assert_eq!(char_index_at(Point::new(-100, -100)), None);
+                assert_eq!(char_index_at(Point::new(500, 500)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn caret_position_in_empty_lines() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let line_height = i32::try_from(font.get_default_line_height()).unwrap();
+        let position = Point::new(30, 20);
+
+        for horizontal_align in [
+            HorizontalAlignment::Left,
+            HorizontalAlignment::Center,
+            HorizontalAlignment::Right,
+        ] {
+            let caret_position = |content, index| {
+                font.caret_position(
+                    content,
+                    position,
+                    VerticalPosition::Baseline,
+                    horizontal_align,
+                    index,
+                )
+                .unwrap()
+            };
+
+            // Right aligned text ends one pixel after the position, like `render_aligned()` does
+            let line_start = match horizontal_align {
+                HorizontalAlignment::Right => position + Point::new(1, 0),
+                _ => position,
+            };
+            assert_eq!(caret_position("", 0), line_start);
+            assert_eq!(
+                caret_position("A\n", 2),
+                line_start + Point::new(0, line_height)
+            );
+            assert_eq!(
+                caret_position("A\n\nB", 3),
+                font.layout(
+                    "A\n\nB",
+                    position,
+                    VerticalPosition::Baseline,
+                    horizontal_align
+                )
+                .nth(3)
+                .unwrap()
+                .unwrap()
+                .origin
+            );
+        }
+    }
+
+    #[test]
+    fn hit_testing_passes_on_lookup_errors() {
+        let font = FontRenderer::new::<fonts::u8g2_font_courB10_tn>();
+
+        assert!(matches!(
+            font.char_index_at(
+                "1a2",
+                Point::zero(),
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left,
+                Point::new(100, 100),
+            ),
+            Err(LookupError::GlyphNotFound('a'))
+        ));
+        assert!(matches!(
+            font.caret_position(
+                "1a2",
+                Point::zero(),
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left,
+                3,
+            ),
+            Err(LookupError::GlyphNotFound('a'))
+        ));
+    }
+}
+
+mod bitmap {
+    use super::*;
+
+    #[test]
+    fn bitmap_matches_rendered_text() {
+        let red = Rgb888::new(237, 28, 36);
+        let helv = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let fonts = [
+            FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_kerning(&KERNING),
+            helv.clone()
+                .with_rotation(FontRotation::Rotate90)
+                .with_scale(2)
+                .with_decorations(TextDecorations::NONE.with_underline(true)),
+            helv.with_synthetic_italic(true).with_synthetic_bold(true),
+        ];
+        let position = Point::new(60, 60);
+
+        for font in fonts {
+            for vertical_pos in [VerticalPosition::Baseline, VerticalPosition::Top] {
+                let mut expected = TestDrawTarget::new(Size::new(200, 200));
+                font.render(
+                    "AVA To\nWa, Agi!",
+                    position,
+                    vertical_pos,
+                    FontColor::Transparent(red),
+                    &mut expected,
+                )
+                .unwrap();
+
+                // Odd stride and garbage in the buffer
+                let mut buffer = [0xa5; 1000];
+                let bitmap = font
+                    .render_to_bitmap("AVA To\nWa, Agi!", vertical_pos, &mut buffer, 13)
+                    .unwrap();
+
+                let dimensions = font
+                    .get_rendered_dimensions("AVA To\nWa, Agi!", position, vertical_pos)
+                    .unwrap()
+                    .bounding_box
+                    .unwrap();
+                assert_eq!(bitmap.size(), dimensions.size);
+                assert_eq!(position + bitmap.offset(), dimensions.top_left);
+
+                let top_left = position + bitmap.offset();
+                for p in expected.bounding_box().points() {
+                    assert_eq!(
+                        bitmap.is_set(p - top_left),
+                        expected.get_pixel(p) == Some(red),
+                        "{:?}",
+                        p
+                    );
+                }
+
+                // Drawing the bitmap only draws the pixels of the text, in its color
+                let mut drawn = TestDrawTarget::new(bitmap.size());
+                bitmap.with_color(red).draw(&mut drawn).unwrap();
+                for p in drawn.bounding_box().points() {
+                    assert_eq!(
+                        drawn.get_pixel(p),
+                        expected.get_pixel(p + top_left),
+                        "{:?}",
+                        p
+                    );
+                }
+
+                let area = Rectangle::new(Point::new(2, 3), Size::new(5, 1000));
+                let mut drawn = TestDrawTarget::new(bitmap.size());
+                bitmap
+                    .with_color(red)
+                    .draw_sub_image(&mut drawn, &area)
+                    .unwrap();
+                for p in drawn.bounding_box().points() {
+                    let expected_color = if area.contains(p + area.top_left) {
+                        expected.get_pixel(p + area.top_left + top_left)
+                    } else {
+                        Some(Rgb888::CSS_WHITE)
+                    };
+                    assert_eq!(drawn.get_pixel(p), expected_color, "{:?}", p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bitmap_checks_buffer_size() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let size = font
+            .get_rendered_dimensions("Hello", Point::zero(), VerticalPosition::Baseline)
+            .unwrap()
+            .bounding_box
+            .unwrap()
+            .size;
+        let stride = Bitmap::min_stride(size.width);
+        let len = Bitmap::required_buffer_len(size, stride);
+
+        assert_eq!(
+            font.render_to_bitmap(
+                "Hello",
+                VerticalPosition::Baseline,
+                &mut vec![0; 1000],
+                stride - 1
+            ),
+            Err(BitmapError::StrideTooSmall { required: stride })
+        );
+        assert_eq!(
+            font.render_to_bitmap(
+                "Hello",
+                VerticalPosition::Baseline,
+                &mut vec![0; len - 1],
+                stride
+            ),
+            Err(BitmapError::BufferTooSmall { required: len })
+        );
+        assert_eq!(
+            font.render_to_bitmap("Hellö", VerticalPosition::Baseline, &mut vec![0; 1000], 10),
+            Err(BitmapError::GlyphNotFound('ö'))
+        );
+
+        // The bitmap doesn't touch the rest of the buffer
+        let mut buffer = vec![0xff; len + 1];
+        let bitmap = font
+            .render_to_bitmap("Hello", VerticalPosition::Baseline, &mut buffer, stride)
+            .unwrap();
+        assert_eq!(bitmap.data().len(), len + 1);
+        assert_eq!(bitmap.stride(), stride);
+        assert_eq!(buffer[len], 0xff);
+
+        let bitmap = font
+            .render_to_bitmap(" ", VerticalPosition::Baseline, &mut [], 0)
+            .unwrap();
+        assert_eq!(bitmap.size(), Size::zero());
+    }
+}
+
+mod prepared_text {
+    use super::*;
+
+    #[test]
+    fn prepared_text_matches_render_aligned() {
+        let red = Rgb888::new(237, 28, 36);
+        let blue = Rgb888::new(63, 72, 204);
+        let helv = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let cases = [
+            (
+                FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>()
+                    .with_kerning(&KERNING)
+                    .with_letter_spacing(1),
+                FontColor::Transparent(red),
+            ),
+            (
+                helv.clone()
+                    .with_rotation(FontRotation::Rotate90)
+                    .with_scale(2)
+                    .with_decorations(TextDecorations::NONE.with_underline(true)),
+                FontColor::WithOutline {
+                    fg: red,
+                    outline: blue,
+                },
+            ),
+            (
+                helv.clone().with_emulated_background(true),
+                FontColor::WithBackground { fg: red, bg: blue },
+            ),
+            (
+                FontRenderer::new::<fonts::u8g2_font_10x20_mf>()
+                    .with_rotation(FontRotation::Rotate180),
+                FontColor::WithBackground { fg: red, bg: blue },
+            ),
+        ];
+        let text = "AVA To\n\nWa, Agi!☃";
+
+        for (font, color) in cases {
+            let font = font.with_ignore_unknown_chars(true);
+            for horizontal_align in [
+                HorizontalAlignment::Left,
+                HorizontalAlignment::Center,
+                HorizontalAlignment::Right,
+            ] {
+                let prepared = font
+                    .prepare::<32>(text, VerticalPosition::Center, horizontal_align)
+                    .unwrap();
+
+                for position in [Point::new(80, 80), Point::new(100, 60)] {
+                    let mut expected = TestDrawTarget::new(Size::new(200, 160));
+                    let expected_dimensions = font
+                        .render_aligned(
+                            text,
+                            position,
+                            VerticalPosition::Center,
+                            horizontal_align,
+                            color,
+                            &mut expected,
+                        )
+                        .unwrap();
+
+                    let mut actual = TestDrawTarget::new(Size::new(200, 160));
+                    let dimensions = prepared.draw(position, color, &mut actual).unwrap();
+
+                    assert_eq!(dimensions, expected_dimensions);
+                    assert_eq!(
+                        prepared.bounding_box(position),
+                        font.get_rendered_dimensions_aligned(
+                            text,
+                            position,
+                            VerticalPosition::Center,
+                            horizontal_align
+                        )
+                        .unwrap()
+                    );
+                    for p in expected.bounding_box().points() {
+                        assert_eq!(actual.get_pixel(p), expected.get_pixel(p), "{:?}", p);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prepared_text_has_fixed_capacity() {
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+
+        assert!(font
+            .prepare::<5>(
+                "Hello",
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left
+            )
+            .is_ok());
+        assert!(font
+            .prepare::<5>(
+                "He\nllo",
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left
+            )
+            .is_ok());
+        assert!(matches!(
+            font.prepare::<4>(
+                "Hello",
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left
+            ),
+            Err(PrepareError::TooManyGlyphs)
+        ));
+        assert!(matches!(
+            font.prepare::<8>(
+                "Hellö",
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left
+            ),
+            Err(PrepareError::GlyphNotFound('ö'))
+        ));
+
+        let prepared = font
+            .prepare::<0>("", VerticalPosition::Baseline, HorizontalAlignment::Left)
+            .unwrap();
+        assert_eq!(prepared.bounding_box(Point::zero()), None);
+        assert!(matches!(
+            prepared.draw(
+                Point::zero(),
+                FontColor::WithBackground {
+                    fg: Rgb888::CSS_RED,
+                    bg: Rgb888::CSS_BLUE
+                },
+                &mut TestDrawTarget::new(Size::new(10, 10)),
+            ),
+            Err(Error::BackgroundColorNotSupported)
+        ));
+    }
+}
+
+mod font_info {
+    use super::*;
+
+    #[test]
+    fn builtin_fonts_list_their_glyphs() {
+        let fonts_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/fonts");
+
+        let mut font_count = 0;
+        for entry in std::fs::read_dir(fonts_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "u8g2font") {
+                let data = std::fs::read(&path).unwrap();
+                let font = FontRenderer::from_data(&data);
+
+                let mut glyph_count = 0;
+                for ch in font.encodings() {
+                    assert!(font.has_glyph(ch), "{} {:?}", path.display(), ch);
+                    glyph_count += 1;
+                }
+                assert_eq!(
+                    glyph_count % 256,
+                    usize::from(font.get_font_info().glyph_count),
+                    "{}",
+                    path.display()
+                );
+                font_count += 1;
+            }
+        }
+
+        assert!(font_count > 0);
+    }
+
+    #[test]
+    fn get_font_info() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_scale(2);
+        let info = font.get_font_info();
+
+        // Unaffected by the configuration of the renderer
+        assert_eq!(i32::from(info.ascent) * 2, font.get_scaled_ascent());
+        assert_eq!(i32::from(info.descent) * 2, font.get_scaled_descent());
+        assert_eq!(
+            font.get_font_bounding_box(VerticalPosition::Baseline),
+            Rectangle::new(
+                Point::new(
+                    i32::from(info.bounding_box_x_offset) * 2,
+                    -(i32::from(info.bounding_box_height) + i32::from(info.bounding_box_y_offset))
+                        * 2,
+                ),
+                Size::new(
+                    u32::try_from(info.bounding_box_width).unwrap() * 2,
+                    u32::try_from(info.bounding_box_height).unwrap() * 2,
+                ),
+            )
+        );
+        assert!(!info.supports_background_color);
+        assert!(info.ascent_of_parentheses >= info.ascent);
+        assert!(info.descent_of_parentheses <= 0);
+        assert_eq!(info.glyph_count, 95);
+        assert!(info.start_pos_upper_a > 0);
+        assert!(info.start_pos_lower_a > info.start_pos_upper_a);
+        assert!(info.start_pos_unicode > info.start_pos_lower_a);
+    }
+
+    #[test]
+    fn checks_coverage_of_text() {
+        static CYRILLIC: FontRenderer = FontRenderer::new::<fonts::u8g2_font_6x13_t_cyrillic>();
+        let font = FontRenderer::new::<fonts::u8g2_font_6x13_tr>();
+
+        assert!("Hi!".chars().all(|ch| font.has_glyph(ch)));
+        assert!(!"Привет".chars().all(|ch| font.has_glyph(ch)));
+        assert!(!font.encodings().any(|ch| ch == 'П'));
+
+        // Replacements don't count, fallbacks do
+        let font = font.with_replacement_char(Some('?'));
+        assert!(!font.has_glyph('П'));
+        let font = font.with_fallback(&CYRILLIC);
+        assert!("Hi Привет!".chars().all(|ch| font.has_glyph(ch)));
+        assert!(!font.encodings().any(|ch| ch == 'П'));
+    }
+}
+
+mod missing_chars {
+    use super::*;
+
+    #[test]
+    fn reports_all_missing_chars() {
+        static CYRILLIC: FontRenderer = FontRenderer::new::<fonts::u8g2_font_6x13_t_cyrillic>();
+        let font = FontRenderer::new::<fonts::u8g2_font_6x13_tr>()
+            .with_replacement_char(Some('?'))
+            .with_ignore_unknown_chars(true);
+
+        fn collect_missing<C: Content + Copy>(
+            font: &FontRenderer,
+            content: C,
+        ) -> Vec<(usize, char)> {
+            let mut missing = Vec::new();
+            let count = font.missing_chars(content, |index, ch| missing.push((index, ch)));
+            assert_eq!(count, missing.len());
+            missing
+        }
+
+        let text = "Grüße\nПривет ☃";
+        assert_eq!(
+            collect_missing(&font, text),
+            [
+                (2, 'ü'),
+                (3, 'ß'),
+                (6, 'П'),
+                (7, 'р'),
+                (8, 'и'),
+                (9, 'в'),
+                (10, 'е'),
+                (11, 'т'),
+                (13, '☃')
+            ]
+        );
+
+        // Indices match the layout
+        for (index, ch) in collect_missing(&font, text) {
+            let glyph = font
+                .layout(
+                    text,
+                    Point::new(0, 0),
+                    VerticalPosition::Baseline,
+                    HorizontalAlignment::Left,
+                )
+                .nth(index)
+                .unwrap()
+                .unwrap();
+            assert_eq!(glyph.ch, ch);
+        }
+
+        let font = font.with_fallback(&CYRILLIC);
+        assert_eq!(
+            collect_missing(&font, text),
+            [(2, 'ü'), (3, 'ß'), (13, '☃')]
+        );
+        assert_eq!(collect_missing(&font, '☃'), [(0, '☃')]);
+        assert_eq!(
+            collect_missing(&font, format_args!("{}{}", 'ß', 42)),
+            [(0, 'ß')]
+        );
+        assert_eq!(collect_missing(&font, "Hello\nWorld"), []);
+    }
+}
+
+mod glyph_metrics {
+    use super::*;
+
+    #[test]
+    fn glyph_metrics_match_layout() {
+        fn check(font: &FontRenderer) {
+            let text = "Ag j_(~ ";
+            let position = Point::new(10, 20);
+            for glyph in font.layout(
+                text,
+                position,
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Left,
+            ) {
+                let glyph = glyph.unwrap();
+                let metrics = font.glyph_metrics(glyph.ch).unwrap();
+
+                assert_eq!(
+                    glyph.advance,
+                    Point::new(metrics.advance, 0),
+                    "{:?}",
+                    glyph.ch
+                );
+                let bounding_box = (metrics.width > 0 && metrics.height > 0).then(|| {
+                    Rectangle::new(
+                        glyph.origin
+                            + Point::new(
+                                metrics.offset_x,
+                                -metrics.offset_y - metrics.height as i32,
+                            ),
+                        Size::new(metrics.width, metrics.height),
+                    )
+                });
+                assert_eq!(glyph.bounding_box, bounding_box, "{:?}", glyph.ch);
+            }
+        }
+
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        check(&font);
+        check(&font.clone().with_scale(3));
+        check(
+            &font
+                .clone()
+                .with_synthetic_bold(true)
+                .with_synthetic_italic(true),
+        );
+        check(
+            &font
+                .with_letter_spacing(2)
+                .with_word_spacing(-1)
+                .with_scale(2),
+        );
+
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>().with_synthetic_bold(true);
+        let metrics = font.glyph_metrics('W').unwrap();
+        let unstyled = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>()
+            .glyph_metrics('W')
+            .unwrap();
+        assert_eq!(metrics.width, unstyled.width + 1);
+        assert_eq!(metrics.advance, unstyled.advance + 1);
+    }
+
+    #[test]
+    fn glyph_metrics_of_unknown_chars() {
+        let font = FontRenderer::new::<fonts::u8g2_font_ncenB14_tr>();
+        assert!(matches!(
+            font.glyph_metrics('☃'),
+            Err(LookupError::GlyphNotFound('☃'))
+        ));
+
+        let font = font.with_ignore_unknown_chars(true);
+        assert_eq!(font.glyph_metrics('☃').unwrap(), GlyphMetrics::default());
+
+        let font = font.with_replacement_char(Some('?'));
+        assert_eq!(
+            font.glyph_metrics('☃').unwrap(),
+            font.glyph_metrics('?').unwrap()
+        );
+    }
+}
+
+mod fixed_width {
+    use super::*;
+
+    #[test]
+    fn fixed_width_is_applied_consistently() {
+        let kerning = [KerningPair::new('1', '1', -2)];
+        let font = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+            .with_fixed_width(FixedWidth::Digits(CellWidth::WidestDigit))
+            .with_kerning(&kerning);
+        let text = "10:59\n8:01\nx1111";
+
+        assert_consistent(&font, text);
+
+        // Every digit advances by the widest digit, without kerning
+        let proportional = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>();
+        let widest = ('0'..='9')
+            .map(|digit| proportional.glyph_metrics(digit).unwrap().advance)
+            .max()
+            .unwrap();
+        for number in ["1111", "8888", "1080"] {
+            let dimensions = font
+                .get_rendered_dimensions(number, Point::zero(), VerticalPosition::Baseline)
+                .unwrap();
+            assert_eq!(dimensions.advance.x, 4 * widest);
+        }
+        assert_eq!(
+            font.glyph_metrics('x').unwrap(),
+            proportional.glyph_metrics('x').unwrap()
+        );
+
+        // Right aligned numbers of the same length start at the same position
+        let start = |number| {
+            font.layout(
+                number,
+                Point::new(100, 20),
+                VerticalPosition::Baseline,
+                HorizontalAlignment::Right,
+            )
+            .next()
+            .unwrap()
+            .unwrap()
+            .origin
+        };
+        assert_eq!(start("11:11"), start("08:58"));
+    }
+
+    #[test]
+    fn fixed_width_centers_glyphs_in_cells() {
+        for (rotation, scale) in [(FontRotation::Rotate0, 1), (FontRotation::Rotate90, 2)] {
+            let proportional = FontRenderer::new::<fonts::u8g2_font_helvR08_tr>()
+                .with_rotation(rotation)
+                .with_scale(scale)
+                .with_synthetic_bold(true);
+            let font = proportional
+                .clone()
+                .with_fixed_width(FixedWidth::AllGlyphs(CellWidth::Pixels(12)));
+
+            for ch in ['i', 'W', '1'] {
+                let metrics = proportional.glyph_metrics(ch).unwrap();
+                let shift = (12 * scale as i32 - metrics.advance).div_euclid(2 * scale as i32);
+                assert_eq!(font.glyph_metrics(ch).unwrap().advance, 12 * scale as i32);
+
+                let mut expected = TestDrawTarget::new(Size::new(100, 100));
+                let mut display = TestDrawTarget::new(Size::new(100, 100));
+                let shifted = match rotation {
+                    FontRotation::Rotate0 => Point::new(shift, 0),
+                    _ => Point::new(0, shift),
+                } * scale as i32;
+                proportional
+                    .render(
+                        ch,
+                        Point::new(40, 40) + shifted,
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::CSS_RED),
+                        &mut expected,
+                    )
+                    .unwrap();
+                let rendered = font
+                    .render(
+                        ch,
+                        Point::new(40, 40),
+                        VerticalPosition::Baseline,
+                        FontColor::Transparent(Rgb888::CSS_RED),
+                        &mut display,
+                    )
+                    .unwrap();
+
+                assert!(rendered.bounding_box.is_some());
+                for point in display.bounding_box().points() {
+                    assert_eq!(
+                        display.get_pixel(point),
+                        expected.get_pixel(point),
+                        "{ch:?}"
+                    );
+                }
+            }
+        }
+    }
+}
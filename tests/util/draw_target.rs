@@ -7,6 +7,7 @@ use image::RgbImage;
 pub struct TestDrawTarget {
     size: Size,
     data: Vec<<Self as DrawTarget>::Color>,
+    out_of_bounds_pixels: usize,
 }
 
 impl TestDrawTarget {
@@ -17,6 +18,7 @@ impl TestDrawTarget {
                 <Self as DrawTarget>::Color::WHITE;
                 size.width as usize * size.height as usize
             ],
+            out_of_bounds_pixels: 0,
         }
     }
 
@@ -81,8 +83,15 @@ impl TestDrawTarget {
             {
                 *value = color;
             }
+        } else {
+            self.out_of_bounds_pixels += 1;
         }
     }
+
+    /// The number of pixels that got drawn outside of the display.
+    pub fn out_of_bounds_pixels(&self) -> usize {
+        self.out_of_bounds_pixels
+    }
 }
 
 fn convert_image_to_data_url(img: &RgbImage) -> String {